@@ -1,8 +1,13 @@
 use std::fmt;
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum AST {
-    Word((String, String)),
+    /**
+     * A terminal with the text the player typed for it and the
+     * canonical word, or lemma, it was read as. The words of a phrase
+     * are kept as typed but one space apart.
+     */
+    Word((String, String, String)),
     Tagged(String, Box<AST>),
     Rule(Vec<AST>),
 }
@@ -22,7 +27,7 @@ fn str_ast(ast: &AST) -> String {
 
 fn str_ast_helper(out: &mut Vec<String>, ast: &AST) {
     match ast {
-        AST::Word((a, b)) => out.push(format!("(Word {} {})", a, b)),
+        AST::Word((a, b, _)) => out.push(format!("(Word {} {})", a, b)),
         AST::Tagged(a, b) => {
             out.push(format!("(Tagged {} ", a));
             str_ast_helper(out, b);
@@ -34,7 +39,9 @@ fn str_ast_helper(out: &mut Vec<String>, ast: &AST) {
                 str_ast_helper(out, ast);
                 out.push(" ".to_string());
             }
-            out.pop();
+            if !v.is_empty() {
+                out.pop();
+            }
             out.push(")".to_string());
         }
     }
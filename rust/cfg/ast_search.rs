@@ -12,6 +12,13 @@ impl<'life> ASTSearch<'life> {
         ASTSearch { tree: tree }
     }
 
+    /**
+     * Whether the search found a tree at all.
+     */
+    pub fn exists(&self) -> bool {
+        self.tree.is_some()
+    }
+
     pub fn get_tree(&self, symbol: &str) -> ASTSearch<'life> {
         if let Some(tree) = self.tree {
             return ASTSearch::new(self.get_tree_helper(symbol, tree));
@@ -63,6 +70,28 @@ impl<'life> ASTSearch<'life> {
         }
     }
 
+    /**
+     * Every direct child tagged `symbol`, in sentence order.
+     */
+    pub fn child_trees(&self, symbol: &str) -> Vec<ASTSearch<'life>> {
+        let children = match self.tree {
+            Some(Tagged(_, child)) => match &**child {
+                Rule(children) => children.iter().collect(),
+                _ => Vec::new(),
+            },
+            Some(Rule(children)) => children.iter().collect(),
+            _ => Vec::new(),
+        };
+        children
+            .into_iter()
+            .filter(|child| match child {
+                Tagged(non_terminal, _) => non_terminal == symbol,
+                _ => false,
+            })
+            .map(|child| ASTSearch::new(Some(child)))
+            .collect()
+    }
+
     fn find_tree_in_children(
         &self,
         symbol: &str,
@@ -83,32 +112,82 @@ impl<'life> ASTSearch<'life> {
         }
     }
 
+    /**
+     * The lemma of the first `symbol` terminal.
+     */
     pub fn get_terminal(&self, symbol: &str) -> Option<String> {
+        let (_, word) = self.get_word(symbol)?;
+        Some(word)
+    }
+
+    /**
+     * The text the player typed for the first `symbol` terminal.
+     */
+    pub fn get_origin(&self, symbol: &str) -> Option<String> {
+        let (origin, _) = self.get_word(symbol)?;
+        Some(origin)
+    }
+
+    /**
+     * The lemmas of every `symbol` terminal, in sentence order.
+     */
+    pub fn get_terminals(&self, symbol: &str) -> Vec<String> {
+        let mut terminals = Vec::new();
+        if let Some(tree) = self.tree {
+            self.get_terminals_helper(symbol, tree, &mut terminals);
+        }
+        terminals
+    }
+
+    fn get_terminals_helper(
+        &self,
+        symbol: &str,
+        tree: &'life AST,
+        terminals: &mut Vec<String>,
+    ) {
+        match tree {
+            Word((terminal, _, word)) => {
+                if symbol == terminal {
+                    terminals.push(word.to_string());
+                }
+            }
+            Tagged(_, child) => {
+                self.get_terminals_helper(symbol, child, terminals)
+            }
+            Rule(children) => {
+                for child in children {
+                    self.get_terminals_helper(symbol, child, terminals);
+                }
+            }
+        }
+    }
+
+    fn get_word(&self, symbol: &str) -> Option<(String, String)> {
         if let Some(tree) = self.tree {
-            return self.get_terminal_helper(symbol, tree);
+            return self.get_word_helper(symbol, tree);
         } else {
             return None;
         }
     }
 
-    fn get_terminal_helper(
+    fn get_word_helper(
         &self,
         symbol: &str,
         tree: &'life AST,
-    ) -> Option<String> {
+    ) -> Option<(String, String)> {
         match tree {
-            Word((terminal, origin)) => {
+            Word((terminal, origin, word)) => {
                 if symbol == terminal {
-                    Some(origin.to_string())
+                    Some((origin.to_string(), word.to_string()))
                 } else {
                     None
                 }
             }
-            Tagged(_, child) => self.get_terminal_helper(symbol, child),
+            Tagged(_, child) => self.get_word_helper(symbol, child),
 
             Rule(children) => {
                 for child in children {
-                    let result = self.get_terminal_helper(symbol, child);
+                    let result = self.get_word_helper(symbol, child);
                     if result.is_some() {
                         return result;
                     }
@@ -0,0 +1,89 @@
+use ast::AST;
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+
+/**
+ * A word of the input that is missing from the Lang's words.
+ * `span` is the byte range of the word in the original sentence and
+ * `suggestions` the known words it may be a typo of, closest first.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnknownWord {
+    pub word: String,
+    pub span: Range<usize>,
+    pub suggestions: Vec<String>,
+}
+
+/**
+ * The longest constituent the CYK matrix recognized in a sentence
+ * that did not parse. `tokens` is the range of words it covers.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartialParse {
+    pub symbol: String,
+    pub tokens: Range<usize>,
+    pub ast: AST,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseError {
+    EmptySentence,
+    UnknownWords(Vec<UnknownWord>),
+    UnusableWord {
+        word: String,
+        span: Range<usize>,
+    },
+    /**
+     * Every word is known but no `S` spans the sentence. `expected`
+     * lists the non-terminals that could have followed `partial`,
+     * which is boxed to keep the error small.
+     */
+    BadGrammar {
+        partial: Option<Box<PartialParse>>,
+        expected: Vec<String>,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::EmptySentence => fmt.write_str("Zero length sentence"),
+            ParseError::UnknownWords(words) => {
+                fmt.write_str("Unknown words:")?;
+                for word in words {
+                    write!(fmt, "\nNot a word: {}", word.word)?;
+                    if !word.suggestions.is_empty() {
+                        write!(
+                            fmt,
+                            " (did you mean {}?)",
+                            word.suggestions.join(", ")
+                        )?;
+                    }
+                }
+                Ok(())
+            }
+            ParseError::UnusableWord { word, .. } => {
+                write!(fmt, "Unusable word {}", word)
+            }
+            ParseError::BadGrammar { partial, expected } => {
+                fmt.write_str("Bad grammar")?;
+                if let Some(partial) = partial {
+                    write!(
+                        fmt,
+                        "\nUnderstood {} over words {}..{}",
+                        partial.symbol,
+                        partial.tokens.start,
+                        partial.tokens.end
+                    )?;
+                }
+                if !expected.is_empty() {
+                    write!(fmt, "\nExpected one of: {}", expected.join(", "))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
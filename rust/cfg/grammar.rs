@@ -0,0 +1,470 @@
+use ast::AST;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+/**
+ * A rule as the grammar author wrote it. An empty `rhs` is an
+ * epsilon production.
+ */
+#[derive(Clone)]
+pub struct Production {
+    pub lhs: String,
+    pub rhs: Vec<String>,
+}
+
+#[derive(Clone)]
+enum Slot {
+    Kept,
+    Empty(AST),
+}
+
+/**
+ * A production with some of its nullable symbols left out. The
+ * template records where the left out symbols belong so the AST
+ * can be rebuilt as the author wrote it.
+ */
+pub struct Variant {
+    pub lhs: String,
+    pub rhs: Vec<String>,
+    template: Vec<Slot>,
+    bare_word: bool,
+}
+
+impl Variant {
+    /**
+     * Builds the value of `lhs` from the ASTs matched by `rhs`.
+     */
+    pub fn fill(&self, children: Vec<AST>) -> AST {
+        let mut children = children.into_iter();
+        if self.bare_word {
+            return children.next().unwrap_or(AST::Rule(Vec::new()));
+        }
+        let mut ans: Vec<AST> = Vec::new();
+        for slot in self.template.iter() {
+            match slot {
+                Slot::Kept => {
+                    if let Some(child) = children.next() {
+                        ans.push(child);
+                    }
+                }
+                Slot::Empty(ast) => ans.push(ast.clone()),
+            }
+        }
+        AST::Rule(ans)
+    }
+}
+
+/**
+ * A rule free of epsilons and unit productions. `path` lists the
+ * variants taken from `lhs` down to the one that produced `rhs`.
+ */
+pub struct NormalRule {
+    pub lhs: String,
+    pub rhs: Vec<String>,
+    pub path: Vec<usize>,
+}
+
+pub struct Normalized {
+    pub variants: Vec<Variant>,
+    pub rules: Vec<NormalRule>,
+}
+
+/**
+ * Removes epsilon and unit productions from a grammar. Rules of
+ * length > 2 and terminals inside longer rules are left for the
+ * caller to binarize.
+ */
+pub fn normalize(productions: &Vec<Production>) -> Normalized {
+    let empty = nullable(productions);
+    let variants = expand_variants(productions, &empty);
+
+    let mut rules = Vec::new();
+    let mut seen = HashSet::new();
+    for lhs in productions.iter().map(|x| &x.lhs) {
+        if !seen.insert(lhs) {
+            continue;
+        }
+        for (symbol, path) in unit_closure(&variants, lhs) {
+            for (i, variant) in variants.iter().enumerate() {
+                if variant.lhs != symbol || is_unit(variant) {
+                    continue;
+                }
+                let mut path = path.clone();
+                path.push(i);
+                rules.push(NormalRule {
+                    lhs: lhs.to_string(),
+                    rhs: variant.rhs.clone(),
+                    path: path,
+                });
+            }
+        }
+    }
+
+    Normalized {
+        variants: variants,
+        rules: rules,
+    }
+}
+
+/**
+ * Parses the alternatives of a rule, lowering the `?`, `*` and `+`
+ * operators and parenthesized groups into productions over generated
+ * symbols. `gen` numbers the generated symbols across rules.
+ */
+pub fn parse_rule(
+    lhs: &str,
+    text: &str,
+    gen: &mut usize,
+) -> Result<Vec<Production>, String> {
+    let mut parser = RuleParser {
+        tokens: lex(text),
+        pos: 0,
+    };
+    let alternatives = parser.alternatives()?;
+    if parser.pos < parser.tokens.len() {
+        return Err(format!("Unexpected \"{}\"", parser.tokens[parser.pos]));
+    }
+
+    let mut lowering = Lowering {
+        lhs: lhs,
+        gen: gen,
+        productions: Vec::new(),
+    };
+    lowering.lower_alternatives(lhs, &alternatives);
+    Ok(lowering.productions)
+}
+
+/**
+ * Symbols made up while normalizing or lowering the grammar. They
+ * never appear in the author's AST.
+ */
+pub fn is_generated(symbol: &str) -> bool {
+    symbol.starts_with("_")
+}
+
+/**
+ * Symbols generated for EBNF operators. Their children belong to the
+ * rule the operator was written in.
+ */
+fn is_inline(symbol: &str) -> bool {
+    is_generated(symbol) && !symbol.starts_with("__")
+}
+
+/**
+ * Splices the children of EBNF generated symbols into their parent
+ * so that repetitions read as a flat list.
+ */
+pub fn flatten(ast: AST) -> AST {
+    match ast {
+        AST::Rule(children) => {
+            AST::Rule(children.into_iter().flat_map(splice).collect())
+        }
+        AST::Tagged(symbol, child) => {
+            AST::Tagged(symbol, Box::new(flatten(*child)))
+        }
+        word => word,
+    }
+}
+
+fn splice(ast: AST) -> Vec<AST> {
+    match ast {
+        AST::Tagged(symbol, child) => {
+            if !is_inline(&symbol) {
+                return vec![AST::Tagged(symbol, Box::new(flatten(*child)))];
+            }
+            match flatten(*child) {
+                AST::Rule(children) => children,
+                child => vec![child],
+            }
+        }
+        ast => vec![flatten(ast)],
+    }
+}
+
+pub fn is_terminal(symbol: &str) -> bool {
+    match symbol.chars().next() {
+        Some(c) => c.is_lowercase(),
+        None => false,
+    }
+}
+
+/**
+ * Finds every symbol that can derive the empty string along with the
+ * AST it derives it with.
+ */
+fn nullable(productions: &Vec<Production>) -> HashMap<String, AST> {
+    let mut empty: HashMap<String, AST> = HashMap::new();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for production in productions.iter() {
+            if empty.contains_key(&production.lhs) {
+                continue;
+            }
+            if production.rhs.iter().all(|x| empty.contains_key(x)) {
+                let ast = AST::Rule(
+                    production
+                        .rhs
+                        .iter()
+                        .map(|x| {
+                            AST::Tagged(
+                                x.to_string(),
+                                Box::new(empty[x].clone()),
+                            )
+                        })
+                        .collect(),
+                );
+                empty.insert(production.lhs.to_string(), ast);
+                changed = true;
+            }
+        }
+    }
+    empty
+}
+
+/**
+ * Expands every production into one variant per combination of its
+ * nullable symbols being present or left out.
+ */
+fn expand_variants(
+    productions: &Vec<Production>,
+    empty: &HashMap<String, AST>,
+) -> Vec<Variant> {
+    let mut variants = Vec::new();
+    for production in productions.iter() {
+        let optional: Vec<usize> = (0..production.rhs.len())
+            .filter(|i| empty.contains_key(&production.rhs[*i]))
+            .collect();
+        for mask in 0..(1usize << optional.len()) {
+            let mut rhs = Vec::new();
+            let mut template = Vec::new();
+            for (i, symbol) in production.rhs.iter().enumerate() {
+                let left_out = optional
+                    .iter()
+                    .position(|x| *x == i)
+                    .map_or(false, |bit| mask & (1 << bit) != 0);
+                if left_out {
+                    template.push(Slot::Empty(AST::Tagged(
+                        symbol.to_string(),
+                        Box::new(empty[symbol].clone()),
+                    )));
+                } else {
+                    rhs.push(symbol.to_string());
+                    template.push(Slot::Kept);
+                }
+            }
+            if rhs.is_empty() {
+                continue;
+            }
+            variants.push(Variant {
+                lhs: production.lhs.to_string(),
+                rhs: rhs,
+                template: template,
+                bare_word: production.rhs.len() == 1
+                    && is_terminal(&production.rhs[0]),
+            });
+        }
+    }
+    variants
+}
+
+fn is_unit(variant: &Variant) -> bool {
+    variant.rhs.len() == 1 && !is_terminal(&variant.rhs[0])
+}
+
+/**
+ * Every symbol reachable from `lhs` through unit variants, paired with
+ * each path of variants that reaches it, shortest first. A path never
+ * goes through a symbol twice, so unit cycles do not repeat.
+ */
+fn unit_closure(
+    variants: &Vec<Variant>,
+    lhs: &str,
+) -> Vec<(String, Vec<usize>)> {
+    let mut reached = vec![(lhs.to_string(), Vec::new())];
+    let mut queue = VecDeque::new();
+    queue.push_back(0);
+    while let Some(next) = queue.pop_front() {
+        let (symbol, path) = reached[next].clone();
+        for (i, variant) in variants.iter().enumerate() {
+            if variant.lhs != symbol || !is_unit(variant) {
+                continue;
+            }
+            let target = &variant.rhs[0];
+            let visited = target == lhs
+                || path.iter().any(|x: &usize| variants[*x].rhs[0] == *target);
+            if !visited {
+                let mut path = path.clone();
+                path.push(i);
+                reached.push((target.to_string(), path));
+                queue.push_back(reached.len() - 1);
+            }
+        }
+    }
+    reached
+}
+
+/**
+ * A symbol of a rule as written, before lowering.
+ */
+enum Term {
+    Symbol(String),
+    Group(Vec<Vec<Term>>),
+    Repeat(Box<Term>, char),
+}
+
+fn is_operator(c: char) -> bool {
+    "()|?*+".contains(c)
+}
+
+/**
+ * Splits a rule's alternatives into symbols and operators.
+ */
+fn lex(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut symbol = String::new();
+    for c in text.chars() {
+        if !c.is_whitespace() && !is_operator(c) {
+            symbol.push(c);
+            continue;
+        }
+        if !symbol.is_empty() {
+            tokens.push(symbol);
+            symbol = String::new();
+        }
+        if is_operator(c) {
+            tokens.push(c.to_string());
+        }
+    }
+    if !symbol.is_empty() {
+        tokens.push(symbol);
+    }
+    tokens
+}
+
+struct RuleParser {
+    tokens: Vec<String>,
+    pos: usize,
+}
+
+impl RuleParser {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(|x| x.as_str())
+    }
+
+    fn alternatives(&mut self) -> Result<Vec<Vec<Term>>, String> {
+        let mut alternatives = vec![self.sequence()?];
+        while self.peek() == Some("|") {
+            self.pos += 1;
+            alternatives.push(self.sequence()?);
+        }
+        Ok(alternatives)
+    }
+
+    fn sequence(&mut self) -> Result<Vec<Term>, String> {
+        let mut terms = Vec::new();
+        loop {
+            let mut term = match self.peek() {
+                None | Some(")") | Some("|") => return Ok(terms),
+                Some("(") => {
+                    self.pos += 1;
+                    let group = self.alternatives()?;
+                    if self.peek() != Some(")") {
+                        return Err("Unclosed group".to_string());
+                    }
+                    Term::Group(group)
+                }
+                Some(op) if is_operator_token(op) => {
+                    return Err(format!("Nothing for \"{}\" to apply to", op));
+                }
+                Some(symbol) => {
+                    if !symbol.starts_with(char::is_alphabetic) {
+                        return Err(
+                            "Symbols must start with a letter".to_string()
+                        );
+                    }
+                    Term::Symbol(symbol.to_string())
+                }
+            };
+            self.pos += 1;
+            while let Some(op) = self.peek() {
+                if !is_operator_token(op) {
+                    break;
+                }
+                let op = op.chars().next().unwrap();
+                term = Term::Repeat(Box::new(term), op);
+                self.pos += 1;
+            }
+            terms.push(term);
+        }
+    }
+}
+
+fn is_operator_token(token: &str) -> bool {
+    token == "?" || token == "*" || token == "+"
+}
+
+struct Lowering<'a> {
+    lhs: &'a str,
+    gen: &'a mut usize,
+    productions: Vec<Production>,
+}
+
+impl<'a> Lowering<'a> {
+    fn lower_alternatives(&mut self, lhs: &str, alternatives: &[Vec<Term>]) {
+        for sequence in alternatives {
+            let rhs = sequence.iter().map(|x| self.lower_term(x)).collect();
+            self.push(lhs, rhs);
+        }
+    }
+
+    /**
+     * Lowers a term to the single symbol that matches it. Repetitions
+     * are right recursive: `X*` becomes `G: X G |`.
+     */
+    fn lower_term(&mut self, term: &Term) -> String {
+        match term {
+            Term::Symbol(symbol) => symbol.to_string(),
+            Term::Group(alternatives) => {
+                let name = self.next_name();
+                self.lower_alternatives(&name, alternatives);
+                name
+            }
+            Term::Repeat(term, op) => {
+                let item = self.lower_term(term);
+                let name = self.next_name();
+                match op {
+                    '?' => {
+                        self.push(&name, vec![item]);
+                        self.push(&name, Vec::new());
+                    }
+                    '*' => {
+                        self.push(&name, vec![item, name.to_string()]);
+                        self.push(&name, Vec::new());
+                    }
+                    _ => {
+                        self.push(
+                            &name,
+                            vec![item.to_string(), name.to_string()],
+                        );
+                        self.push(&name, vec![item]);
+                    }
+                }
+                name
+            }
+        }
+    }
+
+    fn push(&mut self, lhs: &str, rhs: Vec<String>) {
+        self.productions.push(Production {
+            lhs: lhs.to_string(),
+            rhs: rhs,
+        });
+    }
+
+    fn next_name(&mut self) -> String {
+        *self.gen += 1;
+        format!("_{}_{}", self.lhs, self.gen)
+    }
+}
@@ -0,0 +1,128 @@
+/**
+ * Numbers up to ten as they are spelt out.
+ */
+pub const NUMBER_WORDS: [&str; 10] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    "ten",
+];
+
+/**
+ * Nouns whose plural does not follow the usual rules.
+ */
+const IRREGULAR_PLURALS: [(&str, &str); 10] = [
+    ("child", "children"),
+    ("fish", "fish"),
+    ("foot", "feet"),
+    ("knife", "knives"),
+    ("leaf", "leaves"),
+    ("man", "men"),
+    ("mouse", "mice"),
+    ("person", "people"),
+    ("sheep", "sheep"),
+    ("woman", "women"),
+];
+
+/**
+ * The plural of a noun, as "apples", "boxes" or "knives". Only the last
+ * word of a phrase is inflected, as in "ice creams".
+ */
+pub fn plural(noun: &str) -> String {
+    if let Some(i) = noun.rfind(' ') {
+        return format!("{}{}", &noun[..=i], plural(&noun[i + 1..]));
+    }
+    if let Some((_, plural)) =
+        IRREGULAR_PLURALS.iter().find(|(single, _)| *single == noun)
+    {
+        return plural.to_string();
+    }
+    let vowel_y = match noun.chars().rev().nth(1) {
+        Some(before_y) => "aeiou".contains(before_y),
+        None => false,
+    };
+    if noun.ends_with('y') && !vowel_y {
+        format!("{}ies", &noun[..noun.len() - 1])
+    } else if ["s", "x", "z", "ch", "sh"]
+        .iter()
+        .any(|x| noun.ends_with(x))
+    {
+        format!("{}es", noun)
+    } else {
+        format!("{}s", noun)
+    }
+}
+
+/**
+ * Whether a word is spelt as the plural of some other word, as "apples"
+ * or "knives". The word it is the plural of need not be known, so this
+ * is a guess from the spelling alone.
+ */
+pub fn is_plural(word: &str) -> bool {
+    let mut singulars: Vec<String> = IRREGULAR_PLURALS
+        .iter()
+        .filter(|(_, plural)| *plural == word)
+        .map(|(single, _)| single.to_string())
+        .collect();
+    for ending in ["ies", "es", "s"].iter() {
+        if let Some(stem) = word.strip_suffix(ending) {
+            singulars.push(stem.to_string());
+            singulars.push(format!("{}y", stem));
+        }
+    }
+    singulars.iter().any(|single| {
+        single != word && !single.is_empty() && plural(single) == word
+    })
+}
+
+/**
+ * Spells out numbers up to ten, as "two". Larger numbers are written in
+ * digits.
+ */
+pub fn number_word(number: usize) -> String {
+    match NUMBER_WORDS.get(number.wrapping_sub(1)) {
+        Some(word) => word.to_string(),
+        None => number.to_string(),
+    }
+}
+
+/**
+ * Reads a number written in digits or spelt out, as "2" or "two".
+ */
+pub fn parse_number(word: &str) -> Option<usize> {
+    word.parse()
+        .ok()
+        .or_else(|| NUMBER_WORDS.iter().position(|x| *x == word).map(|x| x + 1))
+}
+
+#[cfg(test)]
+mod test {
+    use super::is_plural;
+    use super::number_word;
+    use super::parse_number;
+    use super::plural;
+
+    #[test]
+    fn test_plurals() {
+        assert_eq!("apples", plural("apple"));
+        assert_eq!("boxes", plural("box"));
+        assert_eq!("knives", plural("knife"));
+        assert_eq!("berries", plural("berry"));
+        assert_eq!("keys", plural("key"));
+        assert_eq!("ice creams", plural("ice cream"));
+        assert!(is_plural("apples"));
+        assert!(is_plural("boxes"));
+        assert!(is_plural("berries"));
+        assert!(is_plural("knives"));
+        assert!(!is_plural("apple"));
+        assert!(!is_plural("glass"));
+        assert!(!is_plural("sheep"));
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!("two", number_word(2));
+        assert_eq!("12", number_word(12));
+        assert_eq!(Some(12), parse_number("12"));
+        assert_eq!(Some(3), parse_number("three"));
+        assert_eq!(None, parse_number("many"));
+    }
+}
@@ -1,24 +1,86 @@
 use ast::AST;
+use error::ParseError;
+use error::PartialParse;
+use error::UnknownWord;
+use grammar;
+use grammar::Production;
+use grammar::Variant;
+use inflect;
+use normalize::Normalizer;
+use normalize::Token;
+use spelling;
+use std::cmp;
 use std::collections::HashMap;
 use std::fs;
 use std::iter;
 
+/**
+ * A normalized rule's left hand side and, for rules that came from
+ * the author's grammar, the index of the variant path that rebuilds
+ * the author's AST. Generated links have no build.
+ */
+type RuleTarget = (String, Option<usize>);
+
 pub struct Lang {
-    terminals: HashMap<String, Vec<String>>,
-    pairs: HashMap<String, Vec<String>>,
-    words: HashMap<String, String>,
+    terminals: HashMap<String, Vec<RuleTarget>>,
+    pairs: HashMap<String, Vec<RuleTarget>>,
+    words: HashMap<String, Vec<String>>,
+    lexicon: HashMap<String, Vec<String>>,
+    aliases: HashMap<String, String>,
+    lemmas: HashMap<String, String>,
+    normalizer: Normalizer,
     gen: i32,
     genLookup: HashMap<String, String>,
+    productions: Vec<Production>,
+    variants: Vec<Variant>,
+    builds: Vec<Vec<usize>>,
+    preterminals: HashMap<String, String>,
+    inline_gen: usize,
+    longest_phrase: usize,
+    auto_correct: bool,
+    plurals: Option<String>,
+    numerals: Vec<String>,
 }
 
+/**
+ * How many spelling suggestions are offered for an unknown word.
+ */
+const MAX_SUGGESTIONS: usize = 3;
+
+/**
+ * How many unknown words auto correction tries to correct at once,
+ * which keeps the combinations of suggestions it parses few.
+ */
+const MAX_CORRECTED_WORDS: usize = 3;
+
+/**
+ * How many readings of a sentence `parse_sentence_all` derives. Their
+ * number grows exponentially with ambiguity, so only the flattest are
+ * kept for each symbol of each cell.
+ */
+const MAX_READINGS: usize = 16;
+
 impl Lang {
     pub fn new() -> Lang {
         Lang {
             terminals: HashMap::new(),
             pairs: HashMap::new(),
             words: HashMap::new(),
+            lexicon: HashMap::new(),
+            aliases: HashMap::new(),
+            lemmas: HashMap::new(),
+            normalizer: Normalizer::new(),
             gen: 0,
             genLookup: HashMap::new(),
+            productions: Vec::new(),
+            variants: Vec::new(),
+            builds: Vec::new(),
+            preterminals: HashMap::new(),
+            inline_gen: 0,
+            longest_phrase: 1,
+            auto_correct: false,
+            plurals: None,
+            numerals: Vec::new(),
         }
     }
 
@@ -34,7 +96,15 @@ impl Lang {
     // Rules section
 
     /**
-     * Parses and initializes up the Lang's rules.
+     * Parses and initializes up the Lang's rules. Rules may be any
+     * context free grammar: unit rules (`A: B`), empty alternatives
+     * (`A: B |`) and terminals mixed with non-terminals are converted
+     * to Chomsky normal form internally.
+     *
+     * Symbols may be followed by `?` (optional), `*` (any number) or
+     * `+` (at least one) and alternatives may be grouped with
+     * parentheses, as in `NounClause: Count? Adjective* (Noun | noun)`.
+     * Repeated symbols show up as a flat list in the rule's AST.
      */
     pub fn init_rules(&mut self, rules: &str) {
         let rules = rules.lines().filter(|x| !x.is_empty());
@@ -50,106 +120,133 @@ impl Lang {
             if let (&Some(rule_type), &Some(values)) =
                 (&rule_type.as_ref(), &values.as_ref())
             {
-                let values = values.split("|");
-                for value in values {
-                    self.parseRuleValue(rule_type, value);
-                }
+                self.parseRuleValue(rule_type, values);
             } else {
                 eprintln!("[Ignored] Badly formatted rule \"{}\"", rule);
             }
         }
+        self.normalize();
     }
 
     fn parseRuleValue(&mut self, rule_type: &str, value: &str) {
-        let names = value.trim().split_whitespace().collect::<Vec<&str>>();
-        let len_names = names.len();
-        if len_names == 0 {
+        let rule_type = rule_type.trim();
+        if !firstCharUppercase(rule_type) {
             self.rule_parsing_error(
-                "Length must be >=1 for rule",
+                "Rule names must be non-terminal vars",
                 rule_type,
                 value,
             );
             return;
         }
 
-        // non-terminal tuple
-        if len_names > 2 {
-            self.new_n_pair_rule(rule_type, names);
-            return;
+        // an empty alternative is an epsilon rule
+        match grammar::parse_rule(rule_type, value, &mut self.inline_gen) {
+            Ok(productions) => self.productions.extend(productions),
+            Err(reason) => self.rule_parsing_error(&reason, rule_type, value),
         }
+    }
 
-        // non-terminal pair
-        if len_names == 2 {
-            if firstCharUppercase(names[0]) && firstCharUppercase(names[1])
-            {
-                self.new_pair_rule(rule_type, names[0], names[1])
-            } else {
-                self.rule_parsing_error(
-                    "Unions must be between non-terminal vars",
-                    rule_type,
-                    value,
-                );
+    /**
+     * Rebuilds the CYK tables from the author's productions.
+     */
+    fn normalize(&mut self) {
+        self.terminals.clear();
+        self.pairs.clear();
+        self.gen = 0;
+        self.genLookup.clear();
+        self.builds.clear();
+        self.preterminals.clear();
+
+        let normalized = grammar::normalize(&self.productions);
+        for rule in normalized.rules {
+            let build = Some(self.builds.len());
+            self.builds.push(rule.path);
+
+            // terminal definition handling
+            if rule.rhs.len() == 1 {
+                self.new_terminal_rule(&rule.lhs, &rule.rhs[0], build);
+                continue;
             }
-            return;
-        }
 
-        // terminal definition handling
-        if firstCharLowercase(names[0]) {
-            self.new_terminal_rule(rule_type, names[0]);
-        } else {
-            self.rule_parsing_error("No unit rules", rule_type, value);
+            // terminals inside longer rules get their own non-terminal
+            let names: Vec<String> =
+                rule.rhs.iter().map(|x| self.preterminal(x)).collect();
+            let names: Vec<&str> = names.iter().map(|x| x.as_str()).collect();
+            if names.len() == 2 {
+                self.new_pair_rule(&rule.lhs, names[0], names[1], build);
+            } else {
+                self.new_n_pair_rule(&rule.lhs, names, build);
+            }
         }
+        self.variants = normalized.variants;
     }
 
-    fn new_n_pair_rule(&mut self, rule_type: &str, vals: Vec<&str>) {
-        let tvals = vals.clone();
-        // check all non-terminal symbols
-        for sym in tvals {
-            if !firstCharUppercase(sym) {
-                self.rule_parsing_error(
-                    "Unions must be between non-terminal vars",
-                    rule_type,
-                    sym,
-                );
-                return;
-            }
+    fn preterminal(&mut self, symbol: &str) -> String {
+        if !grammar::is_terminal(symbol) {
+            return symbol.to_string();
+        }
+        if let Some(name) = self.preterminals.get(symbol) {
+            return name.to_string();
         }
+        let name = self.next_gen_name(symbol);
+        self.new_terminal_rule(&name, symbol, None);
+        self.preterminals
+            .insert(symbol.to_string(), name.to_string());
+        name
+    }
 
+    fn new_n_pair_rule(
+        &mut self,
+        rule_type: &str,
+        vals: Vec<&str>,
+        build: Option<usize>,
+    ) {
         // chain everything together under generated types
         // except the last value pair
         let mut first = vals[0].to_string();
         let mut second = vals[1];
         let mut gname = self.next_gen_name(rule_type);
-        self.new_pair_rule(&gname, &first, second);
+        self.new_pair_rule(&gname, &first, second, None);
         for i in 2..(vals.len() - 1) {
             first = gname;
             second = vals[i];
             gname = self.next_gen_name(rule_type);
-            self.new_pair_rule(&gname, &first, second);
+            self.new_pair_rule(&gname, &first, second, None);
         }
 
         // the last value pair is the chain together with the last value
         // and is set to the real type so we know what's up.
         first = gname;
         if let Some(second) = vals.last() {
-            self.new_pair_rule(rule_type, &first, second);
+            self.new_pair_rule(rule_type, &first, second, build);
         }
     }
 
-    fn new_pair_rule(&mut self, rule_type: &str, k1: &str, k2: &str) {
+    fn new_pair_rule(
+        &mut self,
+        rule_type: &str,
+        k1: &str,
+        k2: &str,
+        build: Option<usize>,
+    ) {
         let vals = self
             .pairs
             .entry(keyOfPairRule(k1, k2))
             .or_insert(Vec::new());
-        vals.push(rule_type.to_string());
+        vals.push((rule_type.to_string(), build));
     }
 
-    fn new_terminal_rule(&mut self, rule_type: &str, terminal: &str) {
+    fn new_terminal_rule(
+        &mut self,
+        rule_type: &str,
+        terminal: &str,
+        build: Option<usize>,
+    ) {
         let vals = self
             .terminals
             .entry(terminal.to_string())
             .or_insert(Vec::new());
-        vals.push(rule_type.to_string());
+        vals.push((rule_type.to_string(), build));
     }
 
     fn rule_parsing_error(&self, reason: &str, rule_type: &str, value: &str) {
@@ -170,65 +267,399 @@ impl Lang {
 
     // WORDS SECTION
 
+    /**
+     * Parses and initializes the Lang's words. Each line is a word and
+     * its terminal. The word may be a phrase of several words, as in
+     * `ice cream noun`, which is then read as a single token. A word
+     * listed with several terminals may be read as any of them. Words
+     * are matched after both they and the sentence are normalized.
+     *
+     * A line `alias = word`, as in `fruit = apple`, declares a synonym.
+     * The alias is read with the terminals of the word and its AST
+     * carries the word as its lemma.
+     */
     pub fn init_words(&mut self, words: &str) {
         let words = words.lines().filter(|x| !x.is_empty());
         for word in words {
-            let mut pair = word.trim().split_whitespace();
-            if let (Some(word), Some(terminal)) = (pair.next(), pair.next()) {
-                self.words
-                    .entry(word.to_string())
-                    .or_insert(terminal.to_string());
+            if word.contains('=') {
+                self.init_alias(word);
+                continue;
+            }
+            let mut parts: Vec<&str> = word.split_whitespace().collect();
+            if parts.len() < 2 {
+                continue;
+            }
+            if let Some(terminal) = parts.pop() {
+                let phrase = parts.join(" ");
+                let terminals =
+                    self.words.entry(phrase.to_string()).or_insert(Vec::new());
+                if terminals.iter().any(|x| x == terminal) {
+                    eprintln!(
+                        "[Ignored] Duplicate word \"{}\" as {}",
+                        phrase, terminal
+                    );
+                } else {
+                    terminals.push(terminal.to_string());
+                }
+            }
+        }
+        self.index_words();
+    }
+
+    fn init_alias(&mut self, line: &str) {
+        let mut parts = line.split('=');
+        let alias = parts.next().unwrap_or("").split_whitespace();
+        let alias = alias.collect::<Vec<&str>>().join(" ");
+        let lemma = parts.next().unwrap_or("").split_whitespace();
+        let lemma = lemma.collect::<Vec<&str>>().join(" ");
+        if alias.is_empty() || lemma.is_empty() || parts.next().is_some() {
+            eprintln!("[Ignored] Badly formatted alias \"{}\"", line);
+            return;
+        }
+        match self.aliases.get(&alias) {
+            Some(known) if known == &lemma => {
+                eprintln!("[Ignored] Duplicate alias \"{}\"", line);
+            }
+            Some(known) => eprintln!(
+                "[Ignored] Alias \"{}\" already stands for \"{}\"",
+                alias, known
+            ),
+            None => {
+                self.aliases.insert(alias, lemma);
             }
         }
     }
 
-    pub fn parse_sentence(&self, sentence: &str) -> Result<AST, String> {
-        let mut origins = sentence.split_whitespace().filter(|x| !x.is_empty());
-        let words = origins.clone().filter_map(|x| self.words.get(x));
-        let n = words.clone().count();
+    /**
+     * Replaces how sentences and words are normalized before they are
+     * looked up. Words already loaded are normalized again.
+     */
+    pub fn set_normalizer(&mut self, normalizer: Normalizer) {
+        self.normalizer = normalizer;
+        self.index_words();
+    }
 
-        if n == 0 {
-            return Err("Zero length sentence".to_string());
+    /**
+     * Adds what every vocabulary has to the words. The plural of each
+     * word or alias read as `noun`, as "apples" or "fruits", is read as
+     * `noun` too and carries the singular's lemma. The numbers, spelt out
+     * up to ten or written in digits as "12", are read as `number`.
+     */
+    pub fn set_inflections(&mut self, noun: &str, number: &str) {
+        self.plurals = Some(noun.to_string());
+        self.numerals = vec![number.to_string()];
+        self.index_words();
+    }
+
+    /**
+     * When on, a sentence with unknown words is parsed again with their
+     * spelling suggestions and the one combination that parses is used.
+     */
+    pub fn set_auto_correct(&mut self, auto_correct: bool) {
+        self.auto_correct = auto_correct;
+    }
+
+    /**
+     * Known words and phrases close to `word`, closest first. Typos of
+     * keys next to each other on the keyboard rank higher. Of several
+     * aliases of the same lemma only the closest is offered.
+     */
+    pub fn suggest(&self, word: &str) -> Vec<String> {
+        let word = self.normalizer.normalize_word(word);
+        let limit = spelling::max_distance(word.chars().count());
+        let mut ranked: Vec<(usize, &String)> = self
+            .lexicon
+            .keys()
+            .map(|x| (spelling::distance(&word, x), x))
+            .filter(|(cost, _)| *cost <= limit)
+            .collect();
+        ranked.sort();
+
+        let mut lemmas: Vec<&String> = Vec::new();
+        let mut suggestions = Vec::new();
+        for (_, word) in ranked {
+            let lemma = self.lemmas.get(word).unwrap_or(word);
+            if lemmas.contains(&lemma) {
+                continue;
+            }
+            lemmas.push(lemma);
+            suggestions.push(word.to_string());
+            if suggestions.len() == MAX_SUGGESTIONS {
+                break;
+            }
         }
+        suggestions
+    }
 
-        // Check for and report unknown words
-        if origins.clone().count() != n {
-            let mut error: Vec<String> = Vec::new();
-            error.push("Unknown words:".to_string());
-            for invalid in origins.filter_map(|x| {
-                if self.words.contains_key(x) {
-                    None
-                } else {
-                    Some(x)
+    /**
+     * Rebuilds the lexicon of normalized phrases from the words and
+     * aliases.
+     */
+    fn index_words(&mut self) {
+        self.lexicon.clear();
+        self.longest_phrase = 1;
+        let mut lemmas = HashMap::new();
+        let mut entries: Vec<(String, Vec<String>)> = Vec::new();
+        for (phrase, terminals) in self.words.iter() {
+            entries.push((self.normalize_phrase(phrase), terminals.clone()));
+        }
+        for (alias, lemma) in self.aliases.iter() {
+            let lemma = self.normalize_phrase(lemma);
+            let terminals = match entries.iter().find(|(x, _)| x == &lemma) {
+                Some((_, terminals)) => terminals.clone(),
+                None => {
+                    eprintln!(
+                        "[Ignored] Alias \"{}\" of unknown word \"{}\"",
+                        alias, lemma
+                    );
+                    continue;
+                }
+            };
+            let alias = self.normalize_phrase(alias);
+            lemmas.insert(alias.to_string(), lemma);
+            entries.push((alias, terminals));
+        }
+        if let Some(noun) = &self.plurals {
+            let mut plurals = Vec::new();
+            for (phrase, terminals) in entries.iter() {
+                let plural = inflect::plural(phrase);
+                let known = entries.iter().any(|(x, _)| x == &plural);
+                if !terminals.contains(noun) || known {
+                    continue;
+                }
+                let lemma = lemmas.get(phrase).unwrap_or(phrase).to_string();
+                lemmas.insert(plural.to_string(), lemma);
+                plurals.push((plural, vec![noun.to_string()]));
+            }
+            entries.append(&mut plurals);
+        }
+        if !self.numerals.is_empty() {
+            for word in inflect::NUMBER_WORDS.iter() {
+                entries.push((word.to_string(), self.numerals.clone()));
+            }
+        }
+        self.lemmas = lemmas;
+
+        for (phrase, terminals) in entries {
+            let len = phrase.split(' ').count();
+            self.longest_phrase = cmp::max(self.longest_phrase, len);
+            let entry = self.lexicon.entry(phrase).or_insert(Vec::new());
+            for terminal in terminals {
+                if !entry.contains(&terminal) {
+                    entry.push(terminal);
+                }
+            }
+        }
+    }
+
+    fn normalize_phrase(&self, phrase: &str) -> String {
+        phrase
+            .split(' ')
+            .map(|x| self.normalizer.normalize_word(x))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    /**
+     * Parses a sentence, returning the flattest of its readings without
+     * deriving the others.
+     */
+    pub fn parse_sentence(&self, sentence: &str) -> Result<AST, ParseError> {
+        self.parse_with(sentence, |tokens| self.parse_first(sentence, tokens))
+    }
+
+    /**
+     * Parses a sentence, returning the distinct readings of an ambiguous
+     * sentence, flattest first. At most `MAX_READINGS` of the flattest
+     * are derived. The result is never empty.
+     */
+    pub fn parse_sentence_all(
+        &self,
+        sentence: &str,
+    ) -> Result<Vec<AST>, ParseError> {
+        self.parse_with(sentence, |tokens| self.parse_tokens(sentence, tokens))
+    }
+
+    /**
+     * Tokenizes a sentence and parses its tokens with `parse`, once more
+     * with corrected tokens if it has unknown words and auto correction
+     * is on.
+     */
+    fn parse_with<T, F>(
+        &self,
+        sentence: &str,
+        parse: F,
+    ) -> Result<T, ParseError>
+    where
+        F: Fn(&[Token]) -> Result<T, ParseError>,
+    {
+        let tokens = self.group_phrases(&self.normalizer.tokenize(sentence));
+        match parse(&tokens) {
+            Err(ParseError::UnknownWords(unknown)) => {
+                if self.auto_correct {
+                    let corrected = self.correct(sentence, &tokens, &unknown);
+                    if let Some(tokens) = corrected {
+                        return parse(&tokens);
+                    }
+                }
+                Err(ParseError::UnknownWords(unknown))
+            }
+            result => result,
+        }
+    }
+
+    /**
+     * Tries the combinations of suggestions for a sentence's unknown
+     * words one at a time. Gives the tokens of the only combination that
+     * parses, if there is exactly one, stopping at the second. Sentences
+     * with more than `MAX_CORRECTED_WORDS` unknown words are left as
+     * they are.
+     */
+    fn correct(
+        &self,
+        sentence: &str,
+        tokens: &[Token],
+        unknown: &[UnknownWord],
+    ) -> Option<Vec<Token>> {
+        if unknown.len() > MAX_CORRECTED_WORDS {
+            return None;
+        }
+        let positions = unknown
+            .iter()
+            .map(|word| tokens.iter().position(|x| x.span == word.span))
+            .collect::<Option<Vec<usize>>>()?;
+        let combinations: usize =
+            unknown.iter().map(|x| x.suggestions.len()).product();
+
+        let mut parses = (0..combinations)
+            .map(|combination| {
+                // read the combination as a number with a digit per word
+                let mut candidate = tokens.to_vec();
+                let mut rest = combination;
+                for (position, word) in positions.iter().zip(unknown) {
+                    let count = word.suggestions.len();
+                    candidate[*position].word =
+                        word.suggestions[rest % count].to_string();
+                    rest /= count;
                 }
-            }) {
-                error.push(format!("Not a word: {}", invalid));
+                self.group_phrases(&candidate)
+            })
+            .filter(|tokens| self.parses(sentence, tokens));
+        let tokens = parses.next()?;
+        if parses.next().is_some() {
+            return None;
+        }
+        Some(tokens)
+    }
+
+    /**
+     * Whether the tokens form a sentence, without deriving its AST.
+     */
+    fn parses(&self, sentence: &str, tokens: &[Token]) -> bool {
+        match self.fill_matrix(sentence, tokens) {
+            Ok((matrix, n)) => matrix[index2(n, n, n - 1, 0)].contains_key("S"),
+            Err(_) => false,
+        }
+    }
+
+    fn parse_first(
+        &self,
+        sentence: &str,
+        tokens: &[Token],
+    ) -> Result<AST, ParseError> {
+        let (matrix, n) = self.fill_matrix(sentence, tokens)?;
+        let mut memo = HashMap::new();
+        let index = index2(n, n, n - 1, 0);
+        match self.derive_first(&matrix, &mut memo, index, "S") {
+            Some(ast) => Ok(grammar::flatten(ast)),
+            None => Err(self.bad_grammar(&matrix, n)),
+        }
+    }
+
+    fn parse_tokens(
+        &self,
+        sentence: &str,
+        tokens: &[Token],
+    ) -> Result<Vec<AST>, ParseError> {
+        let (matrix, n) = self.fill_matrix(sentence, tokens)?;
+
+        // Derive every sentence AST
+        let mut memo = HashMap::new();
+        let mut asts: Vec<AST> = Vec::new();
+        let index = index2(n, n, n - 1, 0);
+        for ast in self.derive_symbol(&matrix, &mut memo, index, "S") {
+            let ast = grammar::flatten(ast);
+            if !asts.contains(&ast) {
+                asts.push(ast);
             }
-            return Err(error.join("\n"));
+        }
+        if asts.is_empty() {
+            return Err(self.bad_grammar(&matrix, n));
+        }
+        // Prefer the flattest reading, attaching phrases as high as they go
+        asts.sort_by_key(count_nodes);
+        Ok(asts)
+    }
+
+    fn fill_matrix(
+        &self,
+        sentence: &str,
+        tokens: &[Token],
+    ) -> Result<(Vec<CYKCell>, usize), ParseError> {
+        let n = tokens.len();
+
+        if n == 0 {
+            return Err(ParseError::EmptySentence);
         }
 
-        let mut matrix: Vec<HashMap<String, CYKIntermediate>> =
+        // Check for and report unknown words
+        let unknown: Vec<UnknownWord> = tokens
+            .iter()
+            .filter(|token| self.terminals_of(&token.word).is_none())
+            .map(|token| UnknownWord {
+                word: sentence[token.span.clone()].to_string(),
+                span: token.span.clone(),
+                suggestions: self.suggest(&token.word),
+            })
+            .collect();
+        if !unknown.is_empty() {
+            return Err(ParseError::UnknownWords(unknown));
+        }
+
+        let mut matrix: Vec<CYKCell> =
             iter::repeat_with(|| HashMap::new()).take(n * n).collect();
 
-        // Initialize matrix with the sentence
-        let mut s = 0;
-        for word in words {
-            if let (Some(origin), Some(keys)) =
-                (origins.next(), self.terminals.get(word))
-            {
-                let vals = iter::repeat_with(|| {
-                    CYKIntermediate::Word((
-                        word.to_string(),
-                        origin.to_string(),
-                    ))
+        // Initialize matrix with every terminal of each word
+        for (s, token) in tokens.iter().enumerate() {
+            // a phrase keeps the words as typed, one space apart
+            let origin = sentence[token.span.clone()]
+                .split_whitespace()
+                .collect::<Vec<&str>>()
+                .join(" ");
+            let lemma = self.lemmas.get(&token.word).unwrap_or(&token.word);
+            let mut map: CYKCell = HashMap::new();
+            let terminals = self.terminals_of(&token.word);
+            for terminal in terminals.into_iter().flatten() {
+                for (rule_type, build) in
+                    self.terminals.get(terminal).into_iter().flatten()
+                {
+                    map.entry(rule_type.to_string())
+                        .or_insert(Vec::new())
+                        .push(CYKIntermediate::Word((
+                            terminal.to_string(),
+                            origin.to_string(),
+                            lemma.to_string(),
+                            *build,
+                        )));
+                }
+            }
+            if map.is_empty() {
+                return Err(ParseError::UnusableWord {
+                    word: origin.to_string(),
+                    span: token.span.clone(),
                 });
-                let mut map = HashMap::new();
-                map.extend(keys.iter().map(|x| x.to_string()).zip(vals));
-                matrix[index2(n, n, 0, s)] = map;
-            } else {
-                return Err(format!("Unusable word {}", word));
             }
-            s += 1;
+            matrix[index2(n, n, 0, s)] = map;
         }
 
         // Execute CYK algorithm
@@ -252,90 +683,405 @@ impl Lang {
             }
         }
 
-        // Derive a sentence AST
-        if let Some(answer) = matrix[index2(n, n, n - 1, 0)].get("S") {
-            let ast = self.derive_answer(&matrix, answer);
-            if let Some(ast) = ast {
-                return Ok(ast);
+        Ok((matrix, n))
+    }
+
+    /**
+     * The terminals a normalized word or phrase can be, if it is known.
+     */
+    fn terminals_of(&self, word: &str) -> Option<&Vec<String>> {
+        let numeral = !self.numerals.is_empty()
+            && !word.is_empty()
+            && word.chars().all(|c| c.is_ascii_digit());
+        match self.lexicon.get(word) {
+            Some(terminals) => Some(terminals),
+            None if numeral => Some(&self.numerals),
+            None => None,
+        }
+    }
+
+    /**
+     * Joins a sentence's words into the longest phrases known to the
+     * Lang, keeping each phrase's byte range. Words that start no known
+     * phrase are kept on their own.
+     */
+    fn group_phrases(&self, words: &[Token]) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        let mut i = 0;
+        while i < words.len() {
+            let mut len = cmp::min(self.longest_phrase, words.len() - i);
+            let mut phrase = join_words(&words[i..(i + len)]);
+            while len > 1 && !self.lexicon.contains_key(&phrase) {
+                len -= 1;
+                phrase = join_words(&words[i..(i + len)]);
+            }
+            let start = words[i].span.start;
+            let end = words[i + len - 1].span.end;
+            tokens.push(Token {
+                span: start..end,
+                word: phrase,
+            });
+            i += len;
+        }
+        tokens
+    }
+
+    /**
+     * Builds the error for a sentence with no `S` by finding the longest
+     * (then leftmost) cell holding a named non-terminal.
+     */
+    fn bad_grammar(&self, matrix: &Vec<CYKCell>, n: usize) -> ParseError {
+        let mut memo = HashMap::new();
+        for l in (1..(n + 1)).rev() {
+            for s in 0..(n - l + 1) {
+                let index = index2(n, n, l - 1, s);
+                let cell = &matrix[index];
+                let mut symbols: Vec<&String> =
+                    cell.keys().filter(|x| !grammar::is_generated(x)).collect();
+                symbols.sort_by_key(|x| (x.as_str() != "S", x.to_string()));
+                for symbol in symbols {
+                    let ast = match self
+                        .derive_first(matrix, &mut memo, index, symbol)
+                    {
+                        Some(ast) => ast,
+                        None => continue,
+                    };
+                    let partial = PartialParse {
+                        symbol: symbol.to_string(),
+                        tokens: s..(s + l),
+                        ast: grammar::flatten(AST::Tagged(
+                            symbol.to_string(),
+                            Box::new(ast),
+                        )),
+                    };
+                    return ParseError::BadGrammar {
+                        partial: Some(Box::new(partial)),
+                        expected: self.expected_after(cell),
+                    };
+                }
+            }
+        }
+        ParseError::BadGrammar {
+            partial: None,
+            expected: Vec::new(),
+        }
+    }
+
+    /**
+     * Non-terminals that may follow any symbol of `cell`, including the
+     * generated links of longer rules.
+     */
+    fn expected_after(&self, cell: &CYKCell) -> Vec<String> {
+        let mut expected: Vec<String> = Vec::new();
+        for l_sym in cell.keys() {
+            let prefix = keyOfPairRule(l_sym, "");
+            for pair in self.pairs.keys() {
+                if pair.starts_with(&prefix) {
+                    let r_sym = &pair[prefix.len()..];
+                    self.expected_symbols(r_sym, &mut expected);
+                }
+            }
+        }
+        expected.sort();
+        expected.dedup();
+        expected
+    }
+
+    /**
+     * Names `symbol` the way the author wrote it. Symbols generated for
+     * EBNF operators are named by what they may start with.
+     */
+    fn expected_symbols(&self, symbol: &str, expected: &mut Vec<String>) {
+        if let Some(terminal) = self.genLookup.get(symbol) {
+            expected.push(terminal.to_string());
+        } else if !grammar::is_generated(symbol) {
+            expected.push(symbol.to_string());
+        } else {
+            for production in self.productions.iter() {
+                if production.lhs != symbol {
+                    continue;
+                }
+                if let Some(first) = production.rhs.first() {
+                    if first != symbol {
+                        self.expected_symbols(first, expected);
+                    }
+                }
             }
         }
-        return Err("Bad grammar".to_string());
     }
 
     fn cyk_add_pairs_to_matrix(
         &self,
-        matrix: &mut Vec<HashMap<String, CYKIntermediate>>,
-        left: &HashMap<String, CYKIntermediate>,
-        right: &HashMap<String, CYKIntermediate>,
+        matrix: &mut Vec<CYKCell>,
+        left: &CYKCell,
+        right: &CYKCell,
         l_ind: usize,
         r_ind: usize,
         insert_ind: usize,
     ) {
-        for l_sym in left.keys() {
-            for r_sym in right.keys() {
+        // Visit symbols in order so readings come out the same every time
+        let mut l_syms: Vec<&String> = left.keys().collect();
+        l_syms.sort();
+        let mut r_syms: Vec<&String> = right.keys().collect();
+        r_syms.sort();
+        for l_sym in l_syms.iter() {
+            for r_sym in r_syms.iter() {
                 let pair = keyOfPairRule(l_sym, r_sym);
                 if let Some(derivations) = self.pairs.get(&pair) {
-                    for rule_type in derivations {
+                    for (rule_type, build) in derivations {
                         let derivation = CYKIntermediate::Derivation((
                             l_sym.to_string(),
                             r_sym.to_string(),
                             l_ind,
                             r_ind,
+                            *build,
                         ));
-                        matrix[insert_ind]
-                            .insert(rule_type.to_string(), derivation);
+                        let derivations = matrix[insert_ind]
+                            .entry(rule_type.to_string())
+                            .or_insert(Vec::new());
+                        if !derivations.contains(&derivation) {
+                            derivations.push(derivation);
+                        }
                     }
                 }
             }
         }
     }
 
-    fn derive_answer(
+    /**
+     * Every distinct AST for `symbol` at a matrix cell. Results are
+     * memoized per cell since sub-trees are shared between readings.
+     */
+    fn derive_symbol(
         &self,
-        matrix: &Vec<HashMap<String, CYKIntermediate>>,
-        answer: &CYKIntermediate,
+        matrix: &Vec<CYKCell>,
+        memo: &mut HashMap<(usize, String), Vec<AST>>,
+        index: usize,
+        symbol: &str,
+    ) -> Vec<AST> {
+        let key = (index, symbol.to_string());
+        if let Some(asts) = memo.get(&key) {
+            return asts.clone();
+        }
+
+        let mut derived: Vec<AST> = Vec::new();
+        if let Some(answers) = matrix[index].get(symbol) {
+            for answer in answers {
+                derived.extend(self.derive_answers(matrix, memo, answer));
+            }
+        }
+        // the flattest trees are made of the flattest subtrees, so
+        // keeping only those here still gives the flattest sentences
+        derived.sort_by_cached_key(count_nodes);
+        let mut asts: Vec<AST> = Vec::new();
+        for ast in derived {
+            if asts.len() == MAX_READINGS {
+                break;
+            }
+            if !asts.contains(&ast) {
+                asts.push(ast);
+            }
+        }
+        memo.insert(key, asts.clone());
+        asts
+    }
+
+    /**
+     * The flattest AST for `symbol` at a matrix cell, the first of those
+     * `derive_symbol` would give once sorted, without deriving the
+     * others.
+     */
+    fn derive_first(
+        &self,
+        matrix: &Vec<CYKCell>,
+        memo: &mut HashMap<(usize, String), Option<AST>>,
+        index: usize,
+        symbol: &str,
     ) -> Option<AST> {
+        let key = (index, symbol.to_string());
+        if let Some(ast) = memo.get(&key) {
+            return ast.clone();
+        }
+
+        let mut first: Option<AST> = None;
+        for answer in matrix[index].get(symbol).into_iter().flatten() {
+            let ast = match answer {
+                CYKIntermediate::Word((a, b, c, build)) => {
+                    let word = AST::Word((
+                        a.to_string(),
+                        b.to_string(),
+                        c.to_string(),
+                    ));
+                    self.build_answer(*build, vec![word])
+                }
+                CYKIntermediate::Derivation((
+                    l_sym,
+                    r_sym,
+                    l_ind,
+                    r_ind,
+                    build,
+                )) => {
+                    let l_answer =
+                        self.derive_first(matrix, memo, *l_ind, l_sym);
+                    let r_answer =
+                        self.derive_first(matrix, memo, *r_ind, r_sym);
+                    let (l_answer, r_answer) = match (l_answer, r_answer) {
+                        (Some(l_answer), Some(r_answer)) => {
+                            (l_answer, r_answer)
+                        }
+                        _ => continue,
+                    };
+                    let children =
+                        join_answers(l_sym, &l_answer, r_sym, &r_answer);
+                    self.build_answer(*build, children)
+                }
+            };
+            // keep the first of the flattest, as a stable sort would
+            let flatter = match &first {
+                Some(first) => count_nodes(&ast) < count_nodes(first),
+                None => true,
+            };
+            if flatter {
+                first = Some(ast);
+            }
+        }
+        memo.insert(key, first.clone());
+        first
+    }
+
+    fn derive_answers(
+        &self,
+        matrix: &Vec<CYKCell>,
+        memo: &mut HashMap<(usize, String), Vec<AST>>,
+        answer: &CYKIntermediate,
+    ) -> Vec<AST> {
         match answer {
-            CYKIntermediate::Word((a, b)) => {
-                return Some(AST::Word((a.to_string(), b.to_string())));
-            }
-            CYKIntermediate::Derivation((l_sym, r_sym, l_ind, r_ind)) => {
-                let l_val = matrix[l_ind.clone()].get(l_sym)?;
-                let r_val = matrix[r_ind.clone()].get(r_sym)?;
-                let lAnswer = self.derive_answer(matrix, l_val)?;
-                let rAnswer = AST::Tagged(
-                    r_sym.to_string(),
-                    Box::new(self.derive_answer(matrix, r_val)?),
-                );
-
-                // Handle N-Pair
-                if let (Some(c), AST::Rule(lAnswer)) =
-                    (l_sym.chars().next(), lAnswer.clone())
-                {
-                    if c == '_' {
-                        let mut ans: Vec<AST> = Vec::new();
-                        ans.extend(lAnswer);
-                        ans.push(rAnswer);
-                        return Some(AST::Rule(ans));
+            CYKIntermediate::Word((a, b, c, build)) => {
+                let word =
+                    AST::Word((a.to_string(), b.to_string(), c.to_string()));
+                return vec![self.build_answer(*build, vec![word])];
+            }
+            CYKIntermediate::Derivation((
+                l_sym,
+                r_sym,
+                l_ind,
+                r_ind,
+                build,
+            )) => {
+                let l_answers = self.derive_symbol(matrix, memo, *l_ind, l_sym);
+                let r_answers = self.derive_symbol(matrix, memo, *r_ind, r_sym);
+                let mut answers = Vec::new();
+                // both lists are flattest first, so no pair of the i-th
+                // left and j-th right tree is flatter than the pairs
+                // before both, of which there are (i + 1) * (j + 1)
+                for (i, l_answer) in l_answers.iter().enumerate() {
+                    let pairs = MAX_READINGS / (i + 1);
+                    for r_answer in r_answers.iter().take(pairs) {
+                        let children =
+                            join_answers(l_sym, l_answer, r_sym, r_answer);
+                        answers.push(self.build_answer(*build, children));
                     }
                 }
+                answers
+            }
+        }
+    }
 
-                // Normal 2-Pair rule
-                let lAnswer =
-                    AST::Tagged(l_sym.to_string(), Box::new(lAnswer));
-                let mut ans: Vec<AST> = Vec::new();
-                ans.push(lAnswer);
-                ans.push(rAnswer);
-                Some(AST::Rule(ans))
+    /**
+     * Rebuilds the author's AST for a rule from the children it matched,
+     * putting back left out empty symbols and unit rules. Generated
+     * links keep their children as a flat Rule to be spliced by their
+     * parent.
+     */
+    fn build_answer(&self, build: Option<usize>, children: Vec<AST>) -> AST {
+        let path = match build {
+            Some(build) => &self.builds[build],
+            None => {
+                if children.len() == 1 {
+                    if let AST::Word(_) = children[0] {
+                        return children[0].clone();
+                    }
+                }
+                return AST::Rule(children);
             }
+        };
+        let mut variants = path.iter().rev().map(|x| &self.variants[*x]);
+        let mut answer = match variants.next() {
+            Some(variant) => variant.fill(children),
+            None => AST::Rule(children),
+        };
+        for variant in variants {
+            let child =
+                AST::Tagged(variant.rhs[0].to_string(), Box::new(answer));
+            answer = variant.fill(vec![child]);
+        }
+        answer
+    }
+}
+
+/**
+ * Lists the author level children of a pair. Generated chains on the
+ * left are spliced in and generated terminal wrappers are unwrapped.
+ */
+fn join_answers(
+    l_sym: &str,
+    l_answer: &AST,
+    r_sym: &str,
+    r_answer: &AST,
+) -> Vec<AST> {
+    let mut ans: Vec<AST> = Vec::new();
+    match (is_link(l_sym), l_answer) {
+        (true, AST::Rule(children)) => ans.extend(children.iter().cloned()),
+        (true, _) => ans.push(l_answer.clone()),
+        (false, _) => {
+            ans.push(AST::Tagged(l_sym.to_string(), Box::new(l_answer.clone())))
         }
     }
+    if is_link(r_sym) {
+        ans.push(r_answer.clone());
+    } else {
+        ans.push(AST::Tagged(r_sym.to_string(), Box::new(r_answer.clone())));
+    }
+    ans
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 enum CYKIntermediate {
-    Word((String, String)),
-    Derivation((String, String, usize, usize)),
+    Word((String, String, String, Option<usize>)),
+    Derivation((String, String, usize, usize, Option<usize>)),
+}
+
+type CYKCell = HashMap<String, Vec<CYKIntermediate>>;
+
+fn join_words(words: &[Token]) -> String {
+    words
+        .iter()
+        .map(|x| x.word.as_str())
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+/**
+ * The number of tagged nodes in an AST, leaving out the generated
+ * symbols flattening splices away.
+ */
+fn count_nodes(ast: &AST) -> usize {
+    match ast {
+        AST::Word(_) => 0,
+        AST::Tagged(symbol, child) => {
+            let tagged = if grammar::is_generated(symbol) { 0 } else { 1 };
+            tagged + count_nodes(child)
+        }
+        AST::Rule(children) => children.iter().map(count_nodes).sum(),
+    }
+}
+
+/**
+ * Generated chain links and terminal wrappers, as opposed to the
+ * symbols generated for EBNF operators.
+ */
+fn is_link(symbol: &str) -> bool {
+    symbol.starts_with("__")
 }
 
 fn index2(dimRow: usize, dimCol: usize, row: usize, col: usize) -> usize {
@@ -359,18 +1105,15 @@ fn firstCharUppercase(s: &str) -> bool {
     return false;
 }
 
-fn firstCharLowercase(s: &str) -> bool {
-    if let Some(c) = s.chars().next() {
-        if c.is_lowercase() {
-            return true;
-        }
-    }
-    return false;
-}
-
 #[cfg(test)]
 mod test {
+    use super::count_nodes;
     use super::Lang;
+    use super::MAX_READINGS;
+    use ast_search::ASTSearch;
+    use error::ParseError;
+    use error::UnknownWord;
+    use normalize::Normalizer;
 
     fn make_lang() -> Lang {
         Lang::from_file(
@@ -392,32 +1135,587 @@ indefiniteArticle a)) (Tagged ANoun (Word noun table)))))))",
 		   format!("{}", ast));
     }
 
+    /**
+     * A Lang where every prepositional clause may attach to any noun
+     * clause before it.
+     */
+    fn make_attaching_lang() -> Lang {
+        let mut lang = Lang::new();
+        lang.init_rules(
+            "S: Verb NounClause | Verb NounClause PrepClause
+NounClause: Count Noun | NounClause PrepClause
+PrepClause: Prep NounClause
+Verb: verb
+Noun: noun
+Prep: prep
+Count: definiteArticle | indefiniteArticle",
+        );
+        lang.init_words(
+            "eat verb
+on prep
+apple noun
+table noun
+the definiteArticle
+a indefiniteArticle",
+        );
+        lang
+    }
+
+    #[test]
+    fn test_parse_all_returns_every_reading() {
+        let lang = make_attaching_lang();
+        let asts = lang.parse_sentence_all("eat the apple on a table").unwrap();
+        assert_eq!(2, asts.len());
+        assert_eq!(
+            vec![
+                "((Tagged Verb (Word verb eat)) (Tagged NounClause ((Tagged \
+Count (Word definiteArticle the)) (Tagged Noun (Word noun apple)))) (Tagged \
+PrepClause ((Tagged Prep (Word prep on)) (Tagged NounClause ((Tagged Count \
+(Word indefiniteArticle a)) (Tagged Noun (Word noun table)))))))",
+                "((Tagged Verb (Word verb eat)) (Tagged NounClause ((Tagged \
+NounClause ((Tagged Count (Word definiteArticle the)) (Tagged Noun (Word noun \
+apple)))) (Tagged PrepClause ((Tagged Prep (Word prep on)) (Tagged NounClause \
+((Tagged Count (Word indefiniteArticle a)) (Tagged Noun (Word noun \
+table)))))))))",
+            ],
+            asts.iter()
+                .map(|x| format!("{}", x))
+                .collect::<Vec<String>>()
+        );
+    }
+
+    #[test]
+    fn test_parse_derives_only_the_flattest_reading() {
+        let lang = make_attaching_lang();
+        let test = "eat the apple on a table on a table on a table";
+        let asts = lang.parse_sentence_all(test).unwrap();
+        assert_eq!(10, asts.len());
+        assert_eq!(asts[0], lang.parse_sentence(test).unwrap());
+
+        // far too many readings to derive them all
+        let test = format!("eat the apple{}", " on a table".repeat(20));
+        let ast = lang.parse_sentence(&test).unwrap();
+        let search = ASTSearch::new(Some(&ast));
+        assert!(search.child_tree("PrepClause").exists());
+    }
+
+    #[test]
+    fn test_parse_all_derives_a_bounded_number_of_readings() {
+        let lang = make_attaching_lang();
+        let test = format!("eat the apple{}", " on a table".repeat(12));
+        let asts = lang.parse_sentence_all(&test).unwrap();
+        assert_eq!(MAX_READINGS, asts.len());
+        assert_eq!(asts[0], lang.parse_sentence(&test).unwrap());
+        let nodes: Vec<usize> = asts.iter().map(count_nodes).collect();
+        let mut sorted = nodes.clone();
+        sorted.sort();
+        assert_eq!(sorted, nodes);
+    }
+
+    fn make_normalized_lang(rules: &str) -> Lang {
+        let mut lang = Lang::new();
+        lang.init_rules(rules);
+        lang.init_words(
+            "eat verb
+apple noun
+the definiteArticle
+red adjective
+green adjective",
+        );
+        lang
+    }
+
+    #[test]
+    fn test_unit_and_epsilon_rules() {
+        let lang = make_normalized_lang(
+            "S: Verb NounClause
+Verb: verb
+NounClause: Count ANoun
+Count: definiteArticle |
+ANoun: Adjective ANoun | Noun
+Adjective: adjective
+Noun: noun",
+        );
+        let ast = lang.parse_sentence("eat apple").unwrap();
+        assert_eq!(
+            "((Tagged Verb (Word verb eat)) (Tagged NounClause ((Tagged Count \
+()) (Tagged ANoun ((Tagged Noun (Word noun apple)))))))",
+            format!("{}", ast)
+        );
+        let ast = lang.parse_sentence("eat the red green apple").unwrap();
+        assert_eq!(
+            "((Tagged Verb (Word verb eat)) (Tagged NounClause ((Tagged Count \
+(Word definiteArticle the)) (Tagged ANoun ((Tagged Adjective (Word adjective \
+red)) (Tagged ANoun ((Tagged Adjective (Word adjective green)) (Tagged ANoun \
+((Tagged Noun (Word noun apple)))))))))))",
+            format!("{}", ast)
+        );
+    }
+
+    #[test]
+    fn test_unit_rules_keep_every_reading() {
+        let lang = make_normalized_lang(
+            "S: Verb Thing
+Thing: Food | Object
+Food: Noun
+Object: Noun
+Verb: verb
+Noun: noun",
+        );
+        let asts = lang.parse_sentence_all("eat apple").unwrap();
+        assert_eq!(
+            vec![
+                "((Tagged Verb (Word verb eat)) (Tagged Thing ((Tagged Food \
+((Tagged Noun (Word noun apple)))))))",
+                "((Tagged Verb (Word verb eat)) (Tagged Thing ((Tagged Object \
+((Tagged Noun (Word noun apple)))))))",
+            ],
+            asts.iter()
+                .map(|x| format!("{}", x))
+                .collect::<Vec<String>>()
+        );
+    }
+
+    #[test]
+    fn test_terminals_mixed_into_rules() {
+        let lang = make_normalized_lang(
+            "S: verb definiteArticle Noun | verb Noun
+Noun: noun",
+        );
+        let ast = lang.parse_sentence("eat the apple").unwrap();
+        assert_eq!(
+            "((Word verb eat) (Word definiteArticle the) (Tagged Noun (Word \
+noun apple)))",
+            format!("{}", ast)
+        );
+        assert!(!format!("{}", ast).contains("__"));
+        let ast = lang.parse_sentence("eat apple").unwrap();
+        assert_eq!(
+            "((Word verb eat) (Tagged Noun (Word noun apple)))",
+            format!("{}", ast)
+        );
+    }
+
+    #[test]
+    fn test_ebnf_repetition_is_flattened() {
+        let lang = make_normalized_lang(
+            "S: Verb NounClause
+Verb: verb
+NounClause: definiteArticle? Adjective* Noun
+Adjective: adjective
+Noun: noun",
+        );
+        let ast = lang.parse_sentence("eat the red green apple").unwrap();
+        assert_eq!(
+            "((Tagged Verb (Word verb eat)) (Tagged NounClause ((Word \
+definiteArticle the) (Tagged Adjective (Word adjective red)) (Tagged Adjective \
+(Word adjective green)) (Tagged Noun (Word noun apple)))))",
+            format!("{}", ast)
+        );
+        let ast = lang.parse_sentence("eat apple").unwrap();
+        assert_eq!(
+            "((Tagged Verb (Word verb eat)) (Tagged NounClause ((Tagged Noun \
+(Word noun apple)))))",
+            format!("{}", ast)
+        );
+    }
+
+    #[test]
+    fn test_ebnf_groups_and_one_or_more() {
+        let lang = make_normalized_lang(
+            "S: Verb (Count | Adjective+) Noun
+Verb: verb
+Count: definiteArticle
+Adjective: adjective
+Noun: noun",
+        );
+        let ast = lang.parse_sentence("eat red green apple").unwrap();
+        assert_eq!(
+            "((Tagged Verb (Word verb eat)) (Tagged Adjective (Word adjective \
+red)) (Tagged Adjective (Word adjective green)) (Tagged Noun (Word noun \
+apple)))",
+            format!("{}", ast)
+        );
+        assert!(lang.parse_sentence("eat the apple").is_ok());
+        match lang.parse_sentence("eat apple") {
+            Err(ParseError::BadGrammar { expected, .. }) => assert_eq!(
+                vec!["Adjective".to_string(), "Count".to_string()],
+                expected
+            ),
+            _ => panic!("Should have failed"),
+        }
+    }
+
+    #[test]
+    fn test_ebnf_bad_rules_are_ignored() {
+        let lang = make_normalized_lang(
+            "S: Verb (Noun
+S: Verb Noun) | Verb * Noun
+S: Verb Noun
+Verb: verb
+Noun: noun",
+        );
+        assert_eq!(
+            "((Tagged Verb (Word verb eat)) (Tagged Noun (Word noun apple)))",
+            format!("{}", lang.parse_sentence("eat apple").unwrap())
+        );
+        assert!(lang.parse_sentence("eat").is_err());
+    }
+
+    fn make_phrase_lang() -> Lang {
+        let mut lang = Lang::new();
+        lang.init_rules(
+            "S: Verb NounClause
+Verb: verb
+NounClause: definiteArticle? Noun
+Noun: noun",
+        );
+        lang.init_words(
+            "eat verb
+pick up verb
+the definiteArticle
+ice noun
+cream noun
+ice cream noun
+Mystery Ghost noun",
+        );
+        lang
+    }
+
+    #[test]
+    fn test_multi_word_phrases_are_single_tokens() {
+        let lang = make_phrase_lang();
+        let ast = lang.parse_sentence("pick up the ice  cream").unwrap();
+        assert_eq!(
+            "((Tagged Verb (Word verb pick up)) (Tagged NounClause ((Word \
+definiteArticle the) (Tagged Noun (Word noun ice cream)))))",
+            format!("{}", ast)
+        );
+        let ast = lang.parse_sentence("eat Mystery Ghost").unwrap();
+        assert_eq!(
+            "((Tagged Verb (Word verb eat)) (Tagged NounClause ((Tagged Noun \
+(Word noun Mystery Ghost)))))",
+            format!("{}", ast)
+        );
+        assert!(lang.parse_sentence("eat ice").is_ok());
+    }
+
+    #[test]
+    fn test_unknown_word_inside_phrases() {
+        let lang = make_phrase_lang();
+        match lang.parse_sentence("pick up the chocolate ice cream") {
+            Err(ParseError::UnknownWords(words)) => assert_eq!(
+                vec![UnknownWord {
+                    word: "chocolate".to_string(),
+                    span: 12..21,
+                    suggestions: vec![],
+                }],
+                words
+            ),
+            _ => panic!("Should have failed"),
+        }
+        match lang.parse_sentence("pick the Mystery") {
+            Err(ParseError::UnknownWords(words)) => assert_eq!(
+                vec!["pick".to_string(), "Mystery".to_string()],
+                words.into_iter().map(|x| x.word).collect::<Vec<String>>()
+            ),
+            _ => panic!("Should have failed"),
+        }
+    }
+
+    #[test]
+    fn test_words_with_several_terminals() {
+        let mut lang = Lang::new();
+        lang.init_rules(
+            "S: Verb NounClause
+Verb: verb
+NounClause: definiteArticle? Adjective* Noun
+Adjective: adjective
+Noun: noun",
+        );
+        lang.init_words(
+            "light verb
+light noun
+light adjective
+light noun
+the definiteArticle
+lamp noun",
+        );
+        let asts = lang.parse_sentence_all("light the light lamp").unwrap();
+        assert_eq!(
+            vec![
+                "((Tagged Verb (Word verb light)) (Tagged NounClause ((Word \
+definiteArticle the) (Tagged Adjective (Word adjective light)) (Tagged Noun \
+(Word noun lamp)))))"
+            ],
+            asts.iter()
+                .map(|x| format!("{}", x))
+                .collect::<Vec<String>>()
+        );
+        let asts = lang.parse_sentence_all("light the light").unwrap();
+        assert_eq!(1, asts.len());
+        assert_eq!(
+            "((Tagged Verb (Word verb light)) (Tagged NounClause ((Word \
+definiteArticle the) (Tagged Noun (Word noun light)))))",
+            format!("{}", asts[0])
+        );
+    }
+
+    #[test]
+    fn test_normalizes_case_and_punctuation() {
+        let lang = make_lang();
+        let ast = lang.parse_sentence("EAT the Green Apple!").unwrap();
+        assert_eq!(
+            "((Tagged Verb (Word verb EAT)) (Tagged NounClause ((Tagged Count \
+(Word definiteArticle the)) (Tagged ANoun ((Tagged Adjective (Word adjective \
+Green)) (Tagged Noun (Word noun Apple)))))))",
+            format!("{}", ast)
+        );
+        let search = ASTSearch::new(Some(&ast));
+        assert_eq!(Some("apple".to_string()), search.get_terminal("noun"));
+        assert_eq!(Some("Apple".to_string()), search.get_origin("noun"));
+    }
+
+    #[test]
+    fn test_expands_contractions() {
+        let mut lang = Lang::new();
+        lang.init_rules(
+            "S: wh qVerb Prep NounClause
+NounClause: definiteArticle noun
+Prep: prep",
+        );
+        lang.init_words(
+            "what wh
+is qVerb
+on prep
+the definiteArticle
+table noun",
+        );
+        let ast = lang.parse_sentence("What\u{2019}s on the table?").unwrap();
+        let search = ASTSearch::new(Some(&ast));
+        assert_eq!(Some("is".to_string()), search.get_terminal("qVerb"));
+        assert_eq!(
+            Some("What\u{2019}s".to_string()),
+            search.get_origin("qVerb")
+        );
+        assert_eq!(Some("table".to_string()), search.get_origin("noun"));
+    }
+
+    #[test]
+    fn test_without_normalization() {
+        let mut lang = make_lang();
+        lang.set_normalizer(Normalizer::none());
+        assert!(lang.parse_sentence("eat the apple").is_ok());
+        match lang.parse_sentence("Eat the apple.") {
+            Err(ParseError::UnknownWords(words)) => assert_eq!(
+                vec![
+                    UnknownWord {
+                        word: "Eat".to_string(),
+                        span: 0..3,
+                        suggestions: vec!["eat".to_string()],
+                    },
+                    UnknownWord {
+                        word: "apple.".to_string(),
+                        span: 8..14,
+                        suggestions: vec!["apple".to_string()],
+                    },
+                ],
+                words
+            ),
+            _ => panic!("Should have failed"),
+        }
+    }
+
+    #[test]
+    fn test_suggests_close_words() {
+        let lang = make_lang();
+        assert_eq!(vec!["green".to_string()], lang.suggest("greem"));
+        assert_eq!(vec!["apple".to_string()], lang.suggest("Appel"));
+        assert!(lang.suggest("horse").is_empty());
+        let error = lang.parse_sentence("eat the aple").err().unwrap();
+        assert_eq!(
+            "Unknown words:
+Not a word: aple (did you mean apple?)",
+            format!("{}", error)
+        );
+    }
+
+    #[test]
+    fn test_suggests_one_alias_per_lemma() {
+        let mut lang = Lang::new();
+        lang.init_rules("S: noun");
+        lang.init_words("apple noun\napples = apple\nample noun");
+        assert_eq!(
+            vec!["apple".to_string(), "ample".to_string()],
+            lang.suggest("appler")
+        );
+    }
+
+    #[test]
+    fn test_auto_correct_uses_only_parse() {
+        let mut lang = make_lang();
+        assert!(lang.parse_sentence("eat the greem aple").is_err());
+        lang.set_auto_correct(true);
+        let ast = lang.parse_sentence("eat the greem aple").unwrap();
+        let search = ASTSearch::new(Some(&ast));
+        assert_eq!(Some("green".to_string()), search.get_terminal("adjective"));
+        assert_eq!(Some("greem".to_string()), search.get_origin("adjective"));
+        assert_eq!(Some("apple".to_string()), search.get_terminal("noun"));
+    }
+
+    #[test]
+    fn test_auto_correct_gives_up_on_many_typos() {
+        let mut lang = make_lang();
+        lang.set_auto_correct(true);
+        assert!(lang.parse_sentence("eat teh greem aple").is_ok());
+        match lang.parse_sentence("eatt teh greem aple") {
+            Err(ParseError::UnknownWords(words)) => assert_eq!(4, words.len()),
+            _ => panic!("Should have failed"),
+        }
+    }
+
+    #[test]
+    fn test_auto_correct_leaves_ambiguous_typos() {
+        let mut lang = Lang::new();
+        lang.init_rules("S: verb definiteArticle noun");
+        lang.init_words(
+            "eat verb
+the definiteArticle
+cat noun
+car noun",
+        );
+        lang.set_auto_correct(true);
+        match lang.parse_sentence("eat the cax") {
+            Err(ParseError::UnknownWords(words)) => assert_eq!(
+                vec!["car".to_string(), "cat".to_string()],
+                words[0].suggestions
+            ),
+            _ => panic!("Should have failed"),
+        }
+    }
+
+    #[test]
+    fn test_aliases_read_as_their_lemma() {
+        let mut lang = Lang::new();
+        lang.init_rules("S: verb definiteArticle noun");
+        lang.init_words(
+            "take verb
+pick up = take
+grab = take
+the definiteArticle
+apple noun
+fruit = apple
+thing = widget",
+        );
+        let ast = lang.parse_sentence("Pick up the fruit").unwrap();
+        assert_eq!(
+            "((Word verb Pick up) (Word definiteArticle the) (Word noun fruit))",
+            format!("{}", ast)
+        );
+        let search = ASTSearch::new(Some(&ast));
+        assert_eq!(Some("take".to_string()), search.get_terminal("verb"));
+        assert_eq!(Some("apple".to_string()), search.get_terminal("noun"));
+        assert!(lang.parse_sentence("grab the apple").is_ok());
+        assert!(lang.parse_sentence("take the thing").is_err());
+    }
+
+    #[test]
+    fn test_inflections_read_as_their_terminal() {
+        let mut lang = Lang::new();
+        lang.init_rules("S: verb number noun");
+        lang.init_words("take verb\napple noun\nfruit = apple\nsheep noun");
+        assert!(lang.parse_sentence("take 12 apples").is_err());
+        lang.set_inflections("noun", "number");
+        let ast = lang.parse_sentence("take 12 apples").unwrap();
+        let search = ASTSearch::new(Some(&ast));
+        assert_eq!(Some("12".to_string()), search.get_terminal("number"));
+        assert_eq!(Some("apple".to_string()), search.get_terminal("noun"));
+        assert!(lang.parse_sentence("take two apples").is_ok());
+        assert!(lang.parse_sentence("take 1x apples").is_err());
+        assert!(lang.parse_sentence("take two takes").is_err());
+        assert!(lang.parse_sentence("take three sheep").is_ok());
+
+        // aliases have plurals too, read as the lemma
+        let ast = lang.parse_sentence("take two fruits").unwrap();
+        let search = ASTSearch::new(Some(&ast));
+        assert_eq!(Some("apple".to_string()), search.get_terminal("noun"));
+    }
+
     #[test]
     fn test_unknown_words_produces_error() {
         let lang = make_lang();
         let test = "eat my green horse on a table";
         match lang.parse_sentence(test) {
-            Err(error) => assert_eq!(
-                "Unknown words:
-Not a word: my
-Not a word: horse",
-                error
+            Err(ParseError::UnknownWords(words)) => assert_eq!(
+                vec![
+                    UnknownWord {
+                        word: "my".to_string(),
+                        span: 4..6,
+                        suggestions: vec![],
+                    },
+                    UnknownWord {
+                        word: "horse".to_string(),
+                        span: 13..18,
+                        suggestions: vec![],
+                    },
+                ],
+                words
             ),
-            Ok(_) => {
+            _ => {
                 panic!("Should have failed");
             }
         };
     }
 
+    #[test]
+    fn test_unknown_words_error_message() {
+        let lang = make_lang();
+        let test = "eat my green horse on a table";
+        let error = lang.parse_sentence(test).err().unwrap();
+        assert_eq!(
+            "Unknown words:
+Not a word: my
+Not a word: horse",
+            format!("{}", error)
+        );
+    }
+
     #[test]
     fn test_bad_grammar_produces_error() {
         let lang = make_lang();
         let test = "eat apple eat apple";
         match lang.parse_sentence(test) {
-            Err(error) => assert_eq!("Bad grammar", error),
-            Ok(_) => {
+            Err(ParseError::BadGrammar { partial, expected }) => {
+                let partial = partial.unwrap();
+                assert_eq!("S", partial.symbol);
+                assert_eq!(0..2, partial.tokens);
+                assert_eq!(vec!["PrepClause".to_string()], expected);
+            }
+            _ => {
+                panic!("Should have failed");
+            }
+        };
+    }
+
+    #[test]
+    fn test_bad_grammar_reports_partial_noun_clause() {
+        let lang = make_lang();
+        let test = "on the green apple";
+        match lang.parse_sentence(test) {
+            Err(ParseError::BadGrammar { partial, .. }) => {
+                let partial = partial.unwrap();
+                assert_eq!("PrepClause", partial.symbol);
+                assert_eq!(0..4, partial.tokens);
+            }
+            _ => {
                 panic!("Should have failed");
             }
         };
     }
+
+    #[test]
+    fn test_empty_sentence_produces_error() {
+        let lang = make_lang();
+        assert_eq!(Err(ParseError::EmptySentence), lang.parse_sentence("  "));
+    }
 }
@@ -1,3 +1,10 @@
+extern crate unicode_normalization;
+
+pub mod ast;
 pub mod ast_search;
+pub mod error;
+mod grammar;
+pub mod inflect;
 pub mod lang;
-pub mod ast;
+pub mod normalize;
+mod spelling;
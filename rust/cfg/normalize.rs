@@ -0,0 +1,283 @@
+use std::collections::HashMap;
+use std::ops::Range;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/**
+ * What to do with punctuation at the start or end of a word.
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Punctuation {
+    /** Punctuation stays part of the word, as in `apple.` */
+    Keep,
+    /** Punctuation is dropped. */
+    Strip,
+    /** Each punctuation mark becomes a word of its own. */
+    Tokenize,
+}
+
+/**
+ * A normalized word of a sentence. `span` is the byte range of the
+ * text it came from, which several words share when a contraction
+ * is expanded.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Token {
+    pub span: Range<usize>,
+    pub word: String,
+}
+
+/**
+ * Turns what the player typed into the words a Lang looks up.
+ */
+#[derive(Clone)]
+pub struct Normalizer {
+    pub fold_case: bool,
+    pub unicode: bool,
+    pub punctuation: Punctuation,
+    pub contractions: HashMap<String, String>,
+}
+
+const CONTRACTIONS: &[(&str, &str)] = &[
+    ("aren't", "are not"),
+    ("can't", "can not"),
+    ("didn't", "did not"),
+    ("doesn't", "does not"),
+    ("don't", "do not"),
+    ("here's", "here is"),
+    ("how's", "how is"),
+    ("i'll", "i will"),
+    ("i'm", "i am"),
+    ("isn't", "is not"),
+    ("it's", "it is"),
+    ("let's", "let us"),
+    ("that's", "that is"),
+    ("there's", "there is"),
+    ("they're", "they are"),
+    ("we're", "we are"),
+    ("what's", "what is"),
+    ("where's", "where is"),
+    ("who's", "who is"),
+    ("won't", "will not"),
+    ("you're", "you are"),
+];
+
+impl Normalizer {
+    /**
+     * Folds case, applies NFKC, strips punctuation and expands common
+     * English contractions.
+     */
+    pub fn new() -> Normalizer {
+        Normalizer {
+            fold_case: true,
+            unicode: true,
+            punctuation: Punctuation::Strip,
+            contractions: CONTRACTIONS
+                .iter()
+                .map(|(from, to)| (from.to_string(), to.to_string()))
+                .collect(),
+        }
+    }
+
+    /**
+     * Leaves every word exactly as typed.
+     */
+    pub fn none() -> Normalizer {
+        Normalizer {
+            fold_case: false,
+            unicode: false,
+            punctuation: Punctuation::Keep,
+            contractions: HashMap::new(),
+        }
+    }
+
+    /**
+     * Normalizes the text of a single word, without expanding it.
+     */
+    pub fn normalize_word(&self, word: &str) -> String {
+        let mut word = word.to_string();
+        if self.unicode {
+            word = word.nfkc().map(fold_typography).collect();
+        }
+        if self.fold_case {
+            word = word.to_lowercase();
+        }
+        word
+    }
+
+    /**
+     * Splits a sentence into normalized words.
+     */
+    pub fn tokenize(&self, sentence: &str) -> Vec<Token> {
+        let base = sentence.as_ptr() as usize;
+        let mut tokens = Vec::new();
+        for word in sentence.split_whitespace() {
+            let start = word.as_ptr() as usize - base;
+            for piece in self.split_punctuation(word) {
+                let span = (start + piece.start)..(start + piece.end);
+                let text = self.normalize_word(&word[piece]);
+                match self.contractions.get(&text) {
+                    Some(expansion) => {
+                        for word in expansion.split_whitespace() {
+                            tokens.push(Token {
+                                span: span.clone(),
+                                word: word.to_string(),
+                            });
+                        }
+                    }
+                    None => tokens.push(Token {
+                        span: span,
+                        word: text,
+                    }),
+                }
+            }
+        }
+        tokens
+    }
+
+    /**
+     * The byte ranges of a word that make up its words once leading
+     * and trailing punctuation is handled.
+     */
+    fn split_punctuation(&self, word: &str) -> Vec<Range<usize>> {
+        if self.punctuation == Punctuation::Keep {
+            return vec![0..word.len()];
+        }
+        let marks: Vec<(usize, char)> = word.char_indices().collect();
+        let first = marks.iter().position(|(_, c)| is_word_char(*c));
+        let last = marks.iter().rposition(|(_, c)| is_word_char(*c));
+        let (core, lead, trail) = match (first, last) {
+            (Some(first), Some(last)) => {
+                let (end, c) = marks[last];
+                let core = marks[first].0..(end + c.len_utf8());
+                (Some(core), &marks[..first], &marks[(last + 1)..])
+            }
+            _ => (None, &marks[..], &marks[..0]),
+        };
+
+        let mut pieces = Vec::new();
+        let tokenize = self.punctuation == Punctuation::Tokenize;
+        if tokenize {
+            pieces.extend(lead.iter().map(|(i, c)| *i..(i + c.len_utf8())));
+        }
+        pieces.extend(core);
+        if tokenize {
+            pieces.extend(trail.iter().map(|(i, c)| *i..(i + c.len_utf8())));
+        }
+        pieces
+    }
+}
+
+impl Default for Normalizer {
+    fn default() -> Normalizer {
+        Normalizer::new()
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || is_combining_mark(c)
+}
+
+/**
+ * Maps typographic quotes and dashes to their ASCII forms so `what’s`
+ * reads as `what's`.
+ */
+fn fold_typography(c: char) -> char {
+    match c {
+        '\u{2018}' | '\u{2019}' | '\u{02bc}' => '\'',
+        '\u{201c}' | '\u{201d}' => '"',
+        '\u{2010}'..='\u{2015}' => '-',
+        c => c,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Normalizer;
+    use super::Punctuation;
+    use super::Token;
+
+    #[test]
+    fn test_tokenize_punctuation() {
+        let mut normalizer = Normalizer::new();
+        normalizer.punctuation = Punctuation::Tokenize;
+        assert_eq!(
+            vec![
+                Token {
+                    span: 0..2,
+                    word: "is".to_string(),
+                },
+                Token {
+                    span: 3..9,
+                    word: "café".to_string(),
+                },
+                Token {
+                    span: 9..10,
+                    word: "?".to_string(),
+                },
+            ],
+            normalizer.tokenize("Is CAFE\u{301}?")
+        );
+    }
+
+    fn words(normalizer: &Normalizer, sentence: &str) -> Vec<String> {
+        let tokens = normalizer.tokenize(sentence);
+        tokens.into_iter().map(|token| token.word).collect()
+    }
+
+    #[test]
+    fn test_fold_case() {
+        let mut normalizer = Normalizer::new();
+        assert_eq!(vec!["take", "apple"], words(&normalizer, "TAKE Apple"));
+        normalizer.fold_case = false;
+        assert_eq!(vec!["TAKE", "Apple"], words(&normalizer, "TAKE Apple"));
+    }
+
+    #[test]
+    fn test_unicode() {
+        let mut normalizer = Normalizer::new();
+        assert_eq!(
+            vec!["fish", "café"],
+            words(&normalizer, "\u{fb01}sh cafe\u{301}")
+        );
+        normalizer.unicode = false;
+        assert_eq!(
+            vec!["\u{fb01}sh", "cafe\u{301}"],
+            words(&normalizer, "\u{fb01}sh cafe\u{301}")
+        );
+    }
+
+    #[test]
+    fn test_punctuation() {
+        let mut normalizer = Normalizer::new();
+        assert_eq!(vec!["apple"], words(&normalizer, "\"apple!\""));
+        normalizer.punctuation = Punctuation::Keep;
+        assert_eq!(vec!["\"apple!\""], words(&normalizer, "\"apple!\""));
+        normalizer.punctuation = Punctuation::Tokenize;
+        assert_eq!(
+            vec!["\"", "apple", "!", "\""],
+            words(&normalizer, "\"apple!\"")
+        );
+    }
+
+    #[test]
+    fn test_contractions() {
+        let mut normalizer = Normalizer::new();
+        assert_eq!(
+            vec!["do", "not", "eat"],
+            words(&normalizer, "Don\u{2019}t eat")
+        );
+        normalizer.contractions.clear();
+        assert_eq!(vec!["don't", "eat"], words(&normalizer, "Don't eat"));
+    }
+
+    #[test]
+    fn test_spans_count_bytes() {
+        let normalizer = Normalizer::new();
+        let sentence = "don\u{2019}t eat the crème brûlée.";
+        let tokens = normalizer.tokenize(sentence);
+        let spans: Vec<_> = tokens.iter().map(|x| x.span.clone()).collect();
+        assert_eq!(vec![0..7, 0..7, 8..11, 12..15, 16..22, 23..31], spans);
+        assert_eq!("brûlée", &sentence[tokens[5].span.clone()]);
+    }
+}
@@ -0,0 +1,89 @@
+use std::cmp;
+
+/**
+ * Cost of swapping a letter for one next to it on the keyboard. Every
+ * other edit costs twice as much.
+ */
+const ADJACENT_COST: usize = 1;
+const EDIT_COST: usize = 2;
+
+const ROWS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+/**
+ * The most a suggestion may cost for a word of the given length, so
+ * that short words only get single typos corrected.
+ */
+pub fn max_distance(len: usize) -> usize {
+    if len <= 4 {
+        EDIT_COST
+    } else {
+        2 * EDIT_COST
+    }
+}
+
+/**
+ * Edit distance between two words counting insertions, deletions,
+ * substitutions and swaps of neighbouring letters. Substituting a
+ * letter for an adjacent key is cheaper than any other edit.
+ */
+pub fn distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let width = b.len() + 1;
+    let mut costs = vec![0; (a.len() + 1) * width];
+    for (i, cost) in costs.iter_mut().step_by(width).enumerate() {
+        *cost = i * EDIT_COST;
+    }
+    for (j, cost) in costs.iter_mut().take(width).enumerate() {
+        *cost = j * EDIT_COST;
+    }
+    for i in 1..(a.len() + 1) {
+        for j in 1..width {
+            let substitution = if a[i - 1] == b[j - 1] {
+                0
+            } else if adjacent(a[i - 1], b[j - 1]) {
+                ADJACENT_COST
+            } else {
+                EDIT_COST
+            };
+            let mut cost = cmp::min(
+                costs[(i - 1) * width + j - 1] + substitution,
+                cmp::min(
+                    costs[(i - 1) * width + j] + EDIT_COST,
+                    costs[i * width + j - 1] + EDIT_COST,
+                ),
+            );
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                cost =
+                    cmp::min(cost, costs[(i - 2) * width + j - 2] + EDIT_COST);
+            }
+            costs[i * width + j] = cost;
+        }
+    }
+    costs[a.len() * width + b.len()]
+}
+
+fn key_position(c: char) -> Option<(i32, i32)> {
+    for (row, keys) in ROWS.iter().enumerate() {
+        if let Some(col) = keys.find(c) {
+            return Some((row as i32, col as i32));
+        }
+    }
+    None
+}
+
+/**
+ * Whether two keys touch on a QWERTY keyboard. Rows are staggered so
+ * each key touches two keys of the row above and below it.
+ */
+fn adjacent(a: char, b: char) -> bool {
+    match (key_position(a), key_position(b)) {
+        (Some((row_a, col_a)), Some((row_b, col_b))) => match row_b - row_a {
+            0 => (col_a - col_b).abs() == 1,
+            1 => col_b == col_a || col_b == col_a - 1,
+            -1 => col_b == col_a || col_b == col_a + 1,
+            _ => false,
+        },
+        _ => false,
+    }
+}
@@ -8,8 +8,14 @@ extern crate ift;
 extern crate querier;
 
 use cfg::lang::Lang;
+use ift::conversation::Conversation;
+use ift::conversation::Outcome;
+use ift::describe::describe;
+use ift::entity::Entity;
 use ift::scene::Scene;
+use ift::sentence::split_commands;
 use ift::sentence::Sentence;
+use ift::verbs::Verbs;
 use querier::dialogue_tree::*;
 use querier::models::*;
 use std::fs;
@@ -34,7 +40,7 @@ fn query_test() {
         .expect("String conversion of path failed.");
 
     let q = Querier::new_file(&db_path).expect("valid db connection");
-    q.setup_db();
+    q.setup_db().expect("Failed to setup db");
 
     let mut json_path_buffer = PathBuf::from("/tmp");
     json_path_buffer.push("test_dump_json.json");
@@ -47,9 +53,13 @@ fn query_test() {
     q.dump_from_file(&json_path, FileType::JSON)
         .expect("unsuccesful json dump to db");
 
-    let dia = q.get_dialogue(0);
-    println!("dia {:?}", dia.dialogue_string());
-    println!("dia {:?}", dia.dialogue());
+    match q.get_dialogue(0) {
+        Ok(dia) => {
+            println!("dia {:?}", dia.dialogue_string());
+            println!("dia {:?}", dia.dialogue());
+        }
+        Err(error) => println!("{}", error),
+    }
 
     let story = vec![
         Talk {
@@ -90,21 +100,27 @@ fn lang_test() -> Option<()> {
     let mut lang = Lang::new();
     lang.init_rules(&rules);
     lang.init_words(&words);
-    let mut data = json!({
+    lang.set_inflections("noun", "number");
+    let data = json!({
         "children": [
     {"noun": "apple", "adjectives": ["red", "dirty"]},
     {"noun": "table", "children": [
-        {"noun": "table", "adjectives": ["red"]},
-        {"noun": "apple", "adjectives": ["blotchy", "red"]},
-        {"noun": "apple", "adjectives": ["clean"]},
+        {"noun": "table", "adjectives": ["red"], "relation": "on"},
+        {"noun": "apple", "adjectives": ["blotchy", "red"], "relation": "on"},
+        {"noun": "apple", "adjectives": ["clean"], "relation": "on"},
     ]},
+    {"noun": "ghost", "adjectives": ["friendly"], "gender": "female"},
         ],
     });
 
     println!("grammar:\n\n{}", rules);
     println!("words:\n{}", words);
-    println!("the world:\n\n{}\n", data);
+    let world = Entity::from_value(&data).expect("The scene should be valid");
+    println!("the world:\n\n{}\n", describe(&world));
 
+    let mut scene = Scene::new(world);
+    let mut conversation = Conversation::new();
+    let verbs = Verbs::new();
     loop {
         let reader = io::stdin();
         let mut sentence = String::new();
@@ -117,25 +133,56 @@ fn lang_test() -> Option<()> {
             return Some(());
         }
 
-        let sentence = match lang.parse_sentence(&sentence) {
-            Err(error) => {
-                println!("Error:\n{}", error);
-                None
+        for command in split_commands(&sentence) {
+            play(&lang, &verbs, &mut scene, &mut conversation, &command);
+            println!();
+            // later commands wait until the player has answered
+            if conversation.is_pending() {
+                break;
             }
-            Ok(ast) => Sentence::from_ast(&ast),
-        };
-        let scene = Scene::new(&mut data);
-
-        match sentence {
-            Some(sentence) => {
-                if sentence.is_question {
-                    println!("Answer: {}", scene.ask_question(&sentence));
-                } else {
-                    println!("Ask a question")
-                }
+        }
+    }
+}
+
+fn play(
+    lang: &Lang,
+    verbs: &Verbs,
+    scene: &mut Scene,
+    conversation: &mut Conversation,
+    command: &str,
+) {
+    let follow_up = conversation.follow_up_text(scene, lang, command);
+    if let Some(outcome) = follow_up {
+        report(verbs, scene, outcome);
+        return;
+    }
+
+    let readings = match lang.parse_sentence_all(command) {
+        Err(error) => {
+            println!("Error:\n{}", error);
+            return;
+        }
+        Ok(asts) => asts.iter().filter_map(Sentence::from_ast).collect(),
+    };
+    match verbs.choose(scene, readings) {
+        Some(sentence) => {
+            if sentence.is_question {
+                println!("{}", scene.answer(&sentence).to_text(&sentence));
+            } else {
+                let outcome = conversation.resolve(scene, verbs, sentence);
+                report(verbs, scene, outcome);
             }
-            None => println!("Not a sentence"),
         }
-        println!();
+        None => println!("Not a sentence"),
+    }
+}
+
+fn report(verbs: &Verbs, scene: &mut Scene, outcome: Outcome) {
+    match outcome {
+        Outcome::Clarify(prompt) => println!("{}", prompt),
+        Outcome::Resolved(sentence, resolutions, object) => println!(
+            "{}",
+            verbs.execute(scene, &sentence, resolutions, object)
+        ),
     }
 }
@@ -0,0 +1,231 @@
+use cfg::inflect::number_word;
+use cfg::inflect::plural;
+use conversation::clarification;
+use describe::capitalize;
+use describe::definite;
+use describe::list;
+use entity::Entity;
+use scene::INVENTORY;
+use sentence::PrepClause;
+use sentence::Sentence;
+
+/// A node holding another, as the table in "the apple is on the table".
+#[derive(Clone, Debug, PartialEq)]
+pub struct Place {
+    /// How the node holds what is inside it: "in", "on" or "under".
+    pub relation: String,
+    pub node: Entity,
+}
+
+/// What `Scene::answer` found out.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Answer {
+    /// The answer to a question that is not open, as "is the apple red".
+    YesNo(bool),
+    /// The nodes the subject of a question that is not open could be,
+    /// as the apples in "is the apple red" when there are several.
+    Ambiguous(Vec<Entity>),
+    /// The nodes a what or which question asks for, in scene order.
+    Entities(Vec<Entity>),
+    /// Every node a where question asks about, with the places holding it
+    /// from the innermost out.
+    Locations(Vec<(Entity, Vec<Place>)>),
+    /// How many nodes a how many question counted.
+    Count(usize),
+}
+
+impl Answer {
+    /// Puts the answer into words, as in "On the table there are a red
+    /// table, a blotchy red apple and a clean apple."
+    ///
+    /// # Arguements
+    ///
+    /// * `sentence` - The question that was answered.
+    pub fn to_text(&self, sentence: &Sentence) -> String {
+        let place = sentence.prep.as_ref().map(place_phrase);
+        match self {
+            Answer::YesNo(true) => "Yes.".to_string(),
+            Answer::YesNo(false) => "No.".to_string(),
+            Answer::Ambiguous(candidates) => {
+                clarification(sentence.subject(), candidates)
+            }
+            Answer::Entities(found) => {
+                let be = if found.len() == 1 { "is" } else { "are" };
+                match (found.is_empty(), place) {
+                    (true, Some(place)) => {
+                        format!("There is nothing {}.", place)
+                    }
+                    (true, None) => {
+                        "There is nothing like that here.".to_string()
+                    }
+                    (false, Some(place)) => format!(
+                        "{} there {} {}.",
+                        capitalize(&place),
+                        be,
+                        list(found)
+                    ),
+                    (false, None) => format!(
+                        "{} {} {}.",
+                        if found.len() == 1 { "It" } else { "They" },
+                        be,
+                        list(found)
+                    ),
+                }
+            }
+            Answer::Locations(found) => {
+                if found.is_empty() {
+                    return format!(
+                        "You don't see any {} here.",
+                        sentence.subject().origin
+                    );
+                }
+                found
+                    .iter()
+                    .map(|(node, places)| location(node, places))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            }
+            Answer::Count(count) => {
+                let noun = &sentence.subject().noun;
+                let place = match place {
+                    Some(place) => format!(" {}", place),
+                    None => String::new(),
+                };
+                match count {
+                    0 => format!("There are no {}{}.", plural(noun), place),
+                    1 => format!("There is one {}{}.", noun, place),
+                    _ => format!(
+                        "There are {} {}{}.",
+                        number_word(*count),
+                        plural(noun),
+                        place
+                    ),
+                }
+            }
+        }
+    }
+}
+
+/// Says where a node is, as in "The clean apple is on the table."
+fn location(node: &Entity, places: &[Place]) -> String {
+    let held = places
+        .last()
+        .map_or(false, |place| place.node.is_a(INVENTORY));
+    let places = if held {
+        &places[..places.len() - 1]
+    } else {
+        places
+    };
+    let phrases: Vec<String> = places
+        .iter()
+        .map(|place| format!("{} {}", place.relation, definite(&place.node)))
+        .collect();
+    if held && phrases.is_empty() {
+        return format!("You are holding {}.", definite(node));
+    }
+    let node = capitalize(&definite(node));
+    if phrases.is_empty() {
+        format!("{} is here.", node)
+    } else if held {
+        format!("{} is {}, which you are holding.", node, phrases.join(" "))
+    } else {
+        format!("{} is {}.", node, phrases.join(" "))
+    }
+}
+
+/// The prepositional clause as the player put it, as "on the table" or
+/// "on a table".
+fn place_phrase(prep: &PrepClause) -> String {
+    let mut words = vec![prep.prep.to_string()];
+    words.extend(prep.noun_clause.count().map(|x| x.to_string()));
+    words.extend(prep.noun_clause.adjectives.iter().cloned());
+    words.push(prep.noun_clause.origin.to_string());
+    words.join(" ")
+}
+
+#[cfg(test)]
+mod test {
+    use scene::Scene;
+    use sentence::Sentence;
+    use test_util::make_lang;
+    use test_util::test_scene;
+
+    fn ask(scene: &mut Scene, question: &str) -> String {
+        let sentence = Sentence::from_lang(&make_lang(), question).unwrap();
+        scene.answer(&sentence).to_text(&sentence)
+    }
+
+    #[test]
+    fn test_answers_in_words() {
+        let mut scene = Scene::new(test_scene());
+        assert_eq!(
+            "On the table there are a red table, a blotchy red apple and a \
+clean apple.",
+            ask(&mut scene, "what is on the table")
+        );
+        assert_eq!(
+            "There is nothing on the red table.",
+            ask(&mut scene, "what is on the red table")
+        );
+        assert_eq!(
+            "On a table there are a red table, a blotchy red apple and a \
+clean apple.",
+            ask(&mut scene, "what is on a table")
+        );
+        assert_eq!(
+            "They are a dirty red apple and a blotchy red apple.",
+            ask(&mut scene, "which apples are red")
+        );
+        assert_eq!(
+            "It is a clean apple.",
+            ask(&mut scene, "which apples are clean")
+        );
+        assert_eq!(
+            "There are three apples.",
+            ask(&mut scene, "how many apples are there")
+        );
+        assert_eq!(
+            "There are two apples on the table.",
+            ask(&mut scene, "how many apples are on the table")
+        );
+        assert_eq!("Yes.", ask(&mut scene, "is the clean apple edible"));
+        assert_eq!(
+            "Which do you mean, the dirty red apple, the blotchy red apple or \
+the clean apple?",
+            ask(&mut scene, "is the apple edible")
+        );
+
+        let mut scene = Scene::from_value(&json!({
+            "children": [{"noun": "apple", "is": ["edible"]}]
+        }))
+        .unwrap();
+        assert_eq!(
+            "It is an apple.",
+            ask(&mut scene, "which apples are edible")
+        );
+    }
+
+    #[test]
+    fn test_where_answers() {
+        let mut scene = Scene::new(test_scene());
+        assert_eq!(
+            "The clean apple is on the table.",
+            ask(&mut scene, "where is the clean apple")
+        );
+        assert_eq!(
+            "The dirty red apple is here.\nThe blotchy red apple is on the \
+table.\nThe clean apple is on the table.",
+            ask(&mut scene, "where is the apple")
+        );
+        assert_eq!(
+            "You don't see any box here.",
+            ask(&mut scene, "where is the box")
+        );
+        let clean = scene.root().children[1].children[2].id;
+        scene.hold(clean).unwrap();
+        assert_eq!(
+            "You are holding the clean apple.",
+            ask(&mut scene, "where is the clean apple")
+        );
+    }
+}
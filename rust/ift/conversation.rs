@@ -0,0 +1,452 @@
+use cfg::lang::Lang;
+use describe::sort_adjectives;
+use entity::Entity;
+use scene::Resolution;
+use scene::Scene;
+use sentence::NounClause;
+use sentence::Sentence;
+use verbs::Verbs;
+
+/// What came of a player's sentence once its subjects were looked up.
+pub enum Outcome {
+    /// Every subject was resolved, possibly after follow ups. There is
+    /// one resolution for each subject of the sentence, followed by the
+    /// object's if the verb takes one.
+    Resolved(Box<Sentence>, Vec<Resolution>, Option<Resolution>),
+    /// A subject or the object was ambiguous and the player is asked to
+    /// choose.
+    Clarify(String),
+}
+
+struct Pending {
+    sentence: Sentence,
+    resolutions: Vec<Resolution>,
+    object: Option<Resolution>,
+    /// The subject the player is asked about, or the object if it is
+    /// past the last subject.
+    index: usize,
+}
+
+impl Pending {
+    /// The resolution the player is asked about.
+    fn asked(&mut self) -> Option<&mut Resolution> {
+        if self.index < self.resolutions.len() {
+            self.resolutions.get_mut(self.index)
+        } else {
+            self.object.as_mut()
+        }
+    }
+}
+
+/// State carried from one turn of the game to the next.
+pub struct Conversation {
+    pending: Option<Pending>,
+}
+
+impl Conversation {
+    pub fn new() -> Conversation {
+        Conversation { pending: None }
+    }
+
+    /// Whether the last sentence is waiting for the player to choose
+    /// between candidates.
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Resolves the subjects of a new sentence, and its object if the
+    /// verb takes one, asking which one is meant if one matches several
+    /// nodes. Any pending question is dropped.
+    ///
+    /// # Arguements
+    ///
+    /// * `scene` - The scene the subject is looked up in.
+    /// * `verbs` - The verbs, knowing which take an object.
+    /// * `sentence` - The sentence the player entered.
+    pub fn resolve(
+        &mut self,
+        scene: &mut Scene,
+        verbs: &Verbs,
+        sentence: Sentence,
+    ) -> Outcome {
+        self.pending = None;
+        let (resolutions, object) = verbs.resolve(scene, &sentence);
+        self.settle(scene, sentence, resolutions, object)
+    }
+
+    /// Narrows the pending candidates with a fragment such as "the dirty
+    /// one" or "the blotchy apple". If it fits none of them the same
+    /// question is asked again. Returns None if nothing is pending.
+    ///
+    /// # Arguements
+    ///
+    /// * `scene` - The scene that remembers what was chosen.
+    /// * `fragment` - The noun clause the player answered with.
+    pub fn follow_up(
+        &mut self,
+        scene: &mut Scene,
+        fragment: &NounClause,
+    ) -> Option<Outcome> {
+        let mut pending = self.pending.take()?;
+        let candidates = match pending.asked() {
+            Some(Resolution::Ambiguous(candidates)) => candidates.clone(),
+            _ => Vec::new(),
+        };
+        let mut candidates = scene.narrow(fragment, &candidates);
+        // when nothing fits, the same question is asked again
+        let resolution = match candidates.len() {
+            0 => None,
+            1 => Some(Resolution::Unique(candidates.remove(0))),
+            _ => Some(Resolution::Ambiguous(candidates)),
+        };
+        if let Some(resolution) = resolution {
+            scene.mention(&resolution);
+            if let Some(asked) = pending.asked() {
+                *asked = resolution;
+            }
+        }
+        Some(self.settle(
+            scene,
+            pending.sentence,
+            pending.resolutions,
+            pending.object,
+        ))
+    }
+
+    /// Parses the player's text as a follow up to a pending question.
+    /// Returns None if nothing is pending or the text is not a noun
+    /// clause, in which case it should be read as a new sentence.
+    ///
+    /// # Arguements
+    ///
+    /// * `scene` - The scene that remembers what was chosen.
+    /// * `lang` - The Lang to parse the text with.
+    /// * `text` - What the player typed.
+    pub fn follow_up_text(
+        &mut self,
+        scene: &mut Scene,
+        lang: &Lang,
+        text: &str,
+    ) -> Option<Outcome> {
+        if !self.is_pending() {
+            return None;
+        }
+        let fragment = NounClause::from_lang(lang, text)?;
+        self.follow_up(scene, &fragment)
+    }
+
+    /// Asks about the first ambiguous subject, or then the object, if
+    /// there is one.
+    fn settle(
+        &mut self,
+        scene: &Scene,
+        sentence: Sentence,
+        resolutions: Vec<Resolution>,
+        object: Option<Resolution>,
+    ) -> Outcome {
+        let noun_clauses = sentence
+            .subjects
+            .iter()
+            .chain(sentence.prep.iter().map(|x| &x.noun_clause));
+        let asked = noun_clauses
+            .zip(resolutions.iter().chain(object.iter()))
+            .enumerate()
+            .find_map(|(index, (noun_clause, resolution))| match resolution {
+                Resolution::Ambiguous(candidates) => {
+                    let candidates = scene.entities(candidates);
+                    Some((index, clarification(noun_clause, &candidates)))
+                }
+                _ => None,
+            });
+        let (index, prompt) = match asked {
+            Some(asked) => asked,
+            None => {
+                let sentence = Box::new(sentence);
+                return Outcome::Resolved(sentence, resolutions, object);
+            }
+        };
+        self.pending = Some(Pending {
+            sentence: sentence,
+            resolutions: resolutions,
+            object: object,
+            index: index,
+        });
+        Outcome::Clarify(prompt)
+    }
+}
+
+impl Default for Conversation {
+    fn default() -> Conversation {
+        Conversation::new()
+    }
+}
+
+/// Builds "Which do you mean, the dirty red apple or the blotchy red
+/// apple?" by naming each candidate with the adjectives that set it
+/// apart and those the player used, ordered as `describe::label` orders
+/// them.
+pub fn clarification(subject: &NounClause, candidates: &[Entity]) -> String {
+    let descriptions: Vec<String> = candidates
+        .iter()
+        .map(|candidate| {
+            let mut words: Vec<String> = candidate
+                .adjectives
+                .iter()
+                .filter(|x| {
+                    !candidates.iter().all(|other| other.adjectives.contains(x))
+                })
+                .cloned()
+                .collect();
+            words.extend(subject.adjectives.iter().cloned());
+            sort_adjectives(&mut words);
+            words.push(match &candidate.noun {
+                Some(noun) => noun.to_string(),
+                None => subject.noun.to_string(),
+            });
+            format!("the {}", words.join(" "))
+        })
+        .collect();
+    format!("Which do you mean, {}?", join_choices(&descriptions))
+}
+
+fn join_choices(choices: &[String]) -> String {
+    match choices.split_last() {
+        Some((last, rest)) if !rest.is_empty() => {
+            format!("{} or {}", rest.join(", "), last)
+        }
+        _ => choices.join(""),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Conversation;
+    use super::Outcome;
+    use scene::Resolution;
+    use scene::Scene;
+    use sentence::Sentence;
+    use test_util::make_lang;
+    use test_util::test_scene;
+    use verbs::Verbs;
+
+    #[test]
+    fn test_clarifies_ambiguous_subject() {
+        let lang = make_lang();
+        let mut scene = Scene::new(test_scene());
+        let mut conversation = Conversation::new();
+        let verbs = Verbs::new();
+        let sentence = Sentence::from_lang(&lang, "eat the red apple").unwrap();
+        match conversation.resolve(&mut scene, &verbs, sentence) {
+            Outcome::Clarify(prompt) => assert_eq!(
+                "Which do you mean, the dirty red apple or the blotchy red \
+apple?",
+                prompt
+            ),
+            _ => panic!("should ask which apple"),
+        }
+        assert!(conversation.is_pending());
+
+        match conversation.follow_up_text(&mut scene, &lang, "the dirty one") {
+            Some(Outcome::Resolved(sentence, resolutions, _)) => {
+                assert_eq!("eat", sentence.verb);
+                match &resolutions[..] {
+                    [Resolution::Unique(apple)] => assert_eq!(
+                        json!({
+                            "noun": "apple",
+                            "adjectives": ["red", "dirty"],
+                            "is": ["edible"]
+                        }),
+                        scene.get(*apple).unwrap().to_value()
+                    ),
+                    _ => panic!("should resolve the dirty apple"),
+                }
+            }
+            _ => panic!("should resolve the dirty apple"),
+        }
+        assert!(!conversation.is_pending());
+
+        let sentence = Sentence::from_lang(&lang, "eat it").unwrap();
+        match conversation.resolve(&mut scene, &verbs, sentence) {
+            Outcome::Resolved(_, resolutions, _) => match &resolutions[..] {
+                [Resolution::Unique(apple)] => assert_eq!(
+                    vec!["red", "dirty"],
+                    scene.get(*apple).unwrap().adjectives
+                ),
+                _ => panic!("it should be the dirty apple"),
+            },
+            _ => panic!("it should be the dirty apple"),
+        }
+    }
+
+    #[test]
+    fn test_follow_up_with_relative_clause() {
+        let lang = make_lang();
+        let mut scene = Scene::new(test_scene());
+        let mut conversation = Conversation::new();
+        let verbs = Verbs::new();
+        let sentence = Sentence::from_lang(&lang, "eat the red apple").unwrap();
+        conversation.resolve(&mut scene, &verbs, sentence);
+        let text = "the one that is dirty";
+        match conversation.follow_up_text(&mut scene, &lang, text) {
+            Some(Outcome::Resolved(_, resolutions, _)) => {
+                match &resolutions[..] {
+                    [Resolution::Unique(apple)] => assert_eq!(
+                        vec!["red", "dirty"],
+                        scene.get(*apple).unwrap().adjectives
+                    ),
+                    _ => panic!("should resolve the dirty apple"),
+                }
+            }
+            _ => panic!("should resolve the dirty apple"),
+        }
+    }
+
+    #[test]
+    fn test_asks_again_when_nothing_fits() {
+        let lang = make_lang();
+        let mut scene = Scene::new(test_scene());
+        let mut conversation = Conversation::new();
+        let verbs = Verbs::new();
+        let sentence = Sentence::from_lang(&lang, "eat the red apple").unwrap();
+        conversation.resolve(&mut scene, &verbs, sentence);
+        match conversation.follow_up_text(&mut scene, &lang, "the green one") {
+            Some(Outcome::Clarify(prompt)) => assert_eq!(
+                "Which do you mean, the dirty red apple or the blotchy red \
+apple?",
+                prompt
+            ),
+            _ => panic!("should ask which apple again"),
+        }
+        assert!(conversation.is_pending());
+        assert!(conversation
+            .follow_up_text(&mut scene, &lang, "the blotchy one")
+            .is_some());
+        assert!(!conversation.is_pending());
+    }
+
+    #[test]
+    fn test_clarifies_each_ambiguous_subject() {
+        let lang = make_lang();
+        let mut scene = Scene::new(test_scene());
+        let mut conversation = Conversation::new();
+        let verbs = Verbs::new();
+        let sentence = Sentence::from_lang(
+            &lang,
+            "eat the red apple and the table and the clean apple",
+        )
+        .unwrap();
+        match conversation.resolve(&mut scene, &verbs, sentence) {
+            Outcome::Clarify(prompt) => assert_eq!(
+                "Which do you mean, the dirty red apple or the blotchy red \
+apple?",
+                prompt
+            ),
+            _ => panic!("should ask which apple"),
+        }
+        match conversation.follow_up_text(&mut scene, &lang, "the blotchy one")
+        {
+            Some(Outcome::Clarify(prompt)) => {
+                assert_eq!(
+                    "Which do you mean, the table or the red table?",
+                    prompt
+                )
+            }
+            _ => panic!("should ask which table"),
+        }
+        match conversation.follow_up_text(&mut scene, &lang, "the red one") {
+            Some(Outcome::Resolved(_, resolutions, _)) => {
+                let adjectives: Vec<Vec<String>> = resolutions
+                    .iter()
+                    .map(|x| match x {
+                        Resolution::Unique(node) => {
+                            scene.get(*node).unwrap().adjectives.clone()
+                        }
+                        _ => panic!("every subject should be resolved"),
+                    })
+                    .collect();
+                assert_eq!(
+                    vec![vec!["blotchy", "red"], vec!["red"], vec!["clean"]],
+                    adjectives
+                );
+            }
+            _ => panic!("should resolve every subject"),
+        }
+    }
+
+    #[test]
+    fn test_clarifies_ambiguous_object() {
+        let lang = make_lang();
+        let mut scene = Scene::new(test_scene());
+        let mut conversation = Conversation::new();
+        let verbs = Verbs::new();
+        let sentence =
+            Sentence::from_lang(&lang, "put the clean apple on the table")
+                .unwrap();
+        match conversation.resolve(&mut scene, &verbs, sentence) {
+            Outcome::Clarify(prompt) => assert_eq!(
+                "Which do you mean, the table or the red table?",
+                prompt
+            ),
+            _ => panic!("should ask which table"),
+        }
+        match conversation.follow_up_text(&mut scene, &lang, "the red one") {
+            Some(Outcome::Resolved(sentence, resolutions, object)) => {
+                assert_eq!(
+                    "You put the clean apple on the red table.",
+                    verbs.execute(&mut scene, &sentence, resolutions, object)
+                );
+            }
+            _ => panic!("should resolve the red table"),
+        }
+        let sentence =
+            Sentence::from_lang(&lang, "eat the clean apple on the table")
+                .unwrap();
+        match conversation.resolve(&mut scene, &verbs, sentence) {
+            Outcome::Resolved(_, _, object) => assert!(object.is_none()),
+            _ => panic!("eat takes no object"),
+        }
+    }
+
+    #[test]
+    fn test_follow_up_needs_pending_question() {
+        let lang = make_lang();
+        let mut scene = Scene::new(test_scene());
+        let mut conversation = Conversation::new();
+        let verbs = Verbs::new();
+        assert!(conversation
+            .follow_up_text(&mut scene, &lang, "the dirty one")
+            .is_none());
+
+        let sentence =
+            Sentence::from_lang(&lang, "eat the blotchy apple").unwrap();
+        match conversation.resolve(&mut scene, &verbs, sentence) {
+            Outcome::Resolved(_, resolutions, _) => {
+                assert_eq!(1, resolutions.len())
+            }
+            _ => panic!("should resolve directly"),
+        }
+        assert!(!conversation.is_pending());
+    }
+
+    #[test]
+    fn test_new_sentence_is_not_a_follow_up() {
+        let lang = make_lang();
+        let mut scene = Scene::new(test_scene());
+        let mut conversation = Conversation::new();
+        let verbs = Verbs::new();
+        let sentence = Sentence::from_lang(&lang, "eat all apples").unwrap();
+        match conversation.resolve(&mut scene, &verbs, sentence) {
+            Outcome::Resolved(_, resolutions, _) => match &resolutions[..] {
+                [Resolution::Many(found)] => assert_eq!(3, found.len()),
+                _ => panic!("should take every apple"),
+            },
+            _ => panic!("should take every apple"),
+        }
+        let sentence = Sentence::from_lang(&lang, "eat the red apple").unwrap();
+        conversation.resolve(&mut scene, &verbs, sentence);
+        assert!(conversation.is_pending());
+        assert!(conversation
+            .follow_up_text(&mut scene, &lang, "eat the dirty apple")
+            .is_none());
+    }
+}
@@ -0,0 +1,247 @@
+use cfg::inflect::number_word;
+use cfg::inflect::plural;
+use entity::Entity;
+use scene::INVENTORY;
+
+/// Adjectives by the order they go in before a noun: opinion, size, age,
+/// shape, colour, then material. Adjectives not listed are read as
+/// opinions, which come first.
+const ADJECTIVE_ORDER: [&[&str]; 6] = [
+    &[
+        "beautiful",
+        "blotchy",
+        "clean",
+        "dirty",
+        "friendly",
+        "shiny",
+        "ugly",
+    ],
+    &[
+        "big", "huge", "large", "little", "long", "short", "small", "tall",
+    ],
+    &["ancient", "new", "old", "young"],
+    &["flat", "round", "square"],
+    &[
+        "black", "blue", "brown", "green", "grey", "red", "white", "yellow",
+    ],
+    &["glass", "golden", "iron", "silver", "stone", "wooden"],
+];
+
+/// Describes a node and everything it holds, as in "You see the table.
+/// On it are a red table and two clean apples." A node without a noun is
+/// read as the scene itself, so the root describes what is here.
+pub fn describe(node: &Entity) -> String {
+    let mut sentences = Vec::new();
+    match node.noun {
+        Some(_) => {
+            sentences.push(format!("You see {}.", definite(node)));
+            contents(node, "it", &mut sentences);
+        }
+        None => contents(node, "here", &mut sentences),
+    }
+    sentences.join(" ")
+}
+
+/// Adds a sentence for each way the node holds its children, then
+/// describes the children in turn.
+fn contents(node: &Entity, reference: &str, sentences: &mut Vec<String>) {
+    let children = &node.children;
+    let mut kinds: Vec<&str> = Vec::new();
+    for child in children {
+        let kind = child.relation();
+        if !child.is_a(INVENTORY) && !kinds.contains(&kind) {
+            kinds.push(kind);
+        }
+    }
+    for kind in kinds {
+        let held: Vec<Entity> = children
+            .iter()
+            .filter(|x| !x.is_a(INVENTORY) && x.relation() == kind)
+            .cloned()
+            .collect();
+        let be = if held.len() == 1 { "is" } else { "are" };
+        let place = if reference == "here" {
+            "Here".to_string()
+        } else {
+            format!("{} {}", capitalize(kind), reference)
+        };
+        sentences.push(format!("{} {} {}.", place, be, list(&held)));
+    }
+    for child in children {
+        if child.is_a(INVENTORY) {
+            if !child.children.is_empty() {
+                let held = list(&child.children);
+                sentences.push(format!("You are holding {}.", held));
+            }
+        } else {
+            contents(child, &definite(child), sentences);
+        }
+    }
+}
+
+/// Names nodes as a list, grouping alike nodes, as in "a table and two
+/// red apples".
+pub fn list(nodes: &[Entity]) -> String {
+    let mut groups: Vec<(String, &Entity, usize)> = Vec::new();
+    for node in nodes {
+        let label = label(node);
+        match groups.iter_mut().find(|group| group.0 == label) {
+            Some(group) => group.2 += 1,
+            None => groups.push((label, node, 1)),
+        }
+    }
+    let names: Vec<String> = groups
+        .into_iter()
+        .map(|(_, node, number)| {
+            if number == 1 {
+                indefinite(node)
+            } else {
+                let mut words = adjectives(node);
+                words.insert(0, number_word(number));
+                words.push(plural(noun(node)));
+                words.join(" ")
+            }
+        })
+        .collect();
+    join_list(&names)
+}
+
+/// Names a node with a definite article, as in "the dirty red apple".
+pub fn definite(node: &Entity) -> String {
+    format!("the {}", label(node))
+}
+
+/// Names a node with an indefinite article, as in "an apple".
+pub fn indefinite(node: &Entity) -> String {
+    let label = label(node);
+    format!("{} {}", article(&label), label)
+}
+
+/// Names a node without an article, its adjectives in the order English
+/// puts them, as in "dirty red apple".
+pub fn label(node: &Entity) -> String {
+    let mut words = adjectives(node);
+    words.push(noun(node).to_string());
+    words.join(" ")
+}
+
+/// "an" before a vowel, otherwise "a".
+pub fn article(word: &str) -> &'static str {
+    match word.chars().next() {
+        Some('a') | Some('e') | Some('i') | Some('o') | Some('u') => "an",
+        _ => "a",
+    }
+}
+
+pub fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Joins words as a list, as in "a, b and c".
+pub fn join_list(items: &[String]) -> String {
+    match items.split_last() {
+        Some((last, rest)) if !rest.is_empty() => {
+            format!("{} and {}", rest.join(", "), last)
+        }
+        _ => items.join(""),
+    }
+}
+
+fn noun(node: &Entity) -> &str {
+    node.noun.as_ref().map_or("thing", |x| x.as_str())
+}
+
+/// The node's adjectives, sorted into the order English puts them.
+fn adjectives(node: &Entity) -> Vec<String> {
+    let mut adjectives = node.adjectives.clone();
+    sort_adjectives(&mut adjectives);
+    adjectives
+}
+
+/// Sorts adjectives into the order `label` puts them in, as "dirty red"
+/// rather than "red dirty".
+pub fn sort_adjectives(adjectives: &mut [String]) {
+    adjectives.sort_by_key(|adjective| {
+        ADJECTIVE_ORDER
+            .iter()
+            .position(|words| words.contains(&adjective.as_str()))
+            .unwrap_or(0)
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::describe;
+    use super::indefinite;
+    use super::label;
+    use super::list;
+    use test_util::entity;
+    use test_util::test_scene;
+
+    #[test]
+    fn test_names_nodes() {
+        let apple =
+            entity(json!({"noun": "apple", "adjectives": ["red", "dirty"]}));
+        assert_eq!("dirty red apple", label(&apple));
+        assert_eq!("a dirty red apple", indefinite(&apple));
+        assert_eq!("an apple", indefinite(&entity(json!({"noun": "apple"}))));
+        assert_eq!(
+            "a shiny big old wooden box",
+            indefinite(&entity(json!({
+                "noun": "box",
+                "adjectives": ["wooden", "old", "shiny", "big"]
+            })))
+        );
+    }
+
+    #[test]
+    fn test_lists_alike_nodes_together() {
+        let nodes = entity(json!({"children": [
+            {"noun": "apple", "adjectives": ["red"]},
+            {"noun": "table"},
+            {"noun": "apple", "adjectives": ["red"]},
+            {"noun": "knife"},
+            {"noun": "knife"},
+            {"noun": "knife"}
+        ]}));
+        assert_eq!(
+            "two red apples, a table and three knives",
+            list(&nodes.children)
+        );
+        assert_eq!("", list(&[]));
+    }
+
+    #[test]
+    fn test_describes_containment() {
+        let scene = test_scene();
+        assert_eq!(
+            "Here are a dirty red apple, a table and a friendly ghost. On \
+the table are a red table, a blotchy red apple and a clean apple.",
+            describe(&scene)
+        );
+        let table = entity(json!({"noun": "table", "children": [
+            {"noun": "apple", "adjectives": ["clean"], "relation": "on"},
+            {"noun": "box", "relation": "under", "children": [
+                {"noun": "knife"}
+            ]}
+        ]}));
+        assert_eq!(
+            "You see the table. On it is a clean apple. Under it is a box. \
+In the box is a knife.",
+            describe(&table)
+        );
+        let scene = entity(json!({"children": [
+            {"noun": "inventory", "children": [{"noun": "knife"}]},
+            {"noun": "apple"},
+            {"noun": "apple"}
+        ]}));
+        assert_eq!(
+            "Here are two apples. You are holding a knife.",
+            describe(&scene)
+        );
+    }
+}
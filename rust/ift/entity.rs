@@ -0,0 +1,409 @@
+use serde_json::Map;
+use serde_json::Value;
+use std::error::Error;
+use std::fmt;
+
+/// The relation of a node to its parent when it has none.
+pub const IN: &str = "in";
+pub const ON: &str = "on";
+pub const UNDER: &str = "under";
+
+/// The fields an entity may have in the JSON of a scene.
+const FIELDS: [&str; 7] = [
+    "noun",
+    "adjectives",
+    "is",
+    "relation",
+    "gender",
+    "properties",
+    "children",
+];
+
+/// A node of a scene: the scene itself at the root, and everything in it
+/// below.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entity {
+    /// Tells the entity apart from alike ones. It stays the same for the
+    /// life of the scene, wherever the entity is moved.
+    pub id: usize,
+    /// What the entity is, as "apple". Only the root has none.
+    pub noun: Option<String>,
+    pub adjectives: Vec<String>,
+    /// Components such as "edible" or "openable", read from the "is"
+    /// list.
+    pub components: Vec<String>,
+    /// How the entity sits in its parent: "in", "on" or "under". None is
+    /// read as "in".
+    pub relation: Option<String>,
+    /// "male" or "female" for characters "him" and "her" can refer to.
+    pub gender: Option<String>,
+    /// Anything else about the entity, for registered question types
+    /// and verbs to read.
+    pub properties: Map<String, Value>,
+    pub children: Vec<Entity>,
+}
+
+/// Why a scene could not be read.
+#[derive(Clone, Debug, PartialEq)]
+pub enum EntityError {
+    /// The text is not JSON at all.
+    Json(String),
+    /// The JSON is not a valid scene. Every problem found is listed.
+    Invalid(Vec<Invalid>),
+}
+
+/// A problem with one value in the JSON of a scene.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Invalid {
+    /// Where the value is, as "$.children[1].adjectives[0]".
+    pub path: String,
+    pub problem: Problem,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Problem {
+    ExpectedObject,
+    ExpectedArray,
+    ExpectedString,
+    /// An entity other than the root has no noun.
+    MissingNoun,
+    /// A field that is not one an entity has, often a typo.
+    UnknownField(String),
+    /// A relation other than "in", "on" and "under".
+    UnknownRelation(String),
+}
+
+impl Entity {
+    /// An entity with just a noun.
+    pub fn new(id: usize, noun: &str) -> Entity {
+        Entity {
+            id: id,
+            noun: Some(noun.to_string()),
+            adjectives: Vec::new(),
+            components: Vec::new(),
+            relation: None,
+            gender: None,
+            properties: Map::new(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Reads a scene from JSON text. See `from_value`.
+    pub fn from_json(text: &str) -> Result<Entity, EntityError> {
+        let value: Value = serde_json::from_str(text)
+            .map_err(|error| EntityError::Json(error.to_string()))?;
+        Entity::from_value(&value)
+    }
+
+    /// Reads a scene from its JSON value, checking every entity in it.
+    /// Ids are given out in the order entities appear, the root's being
+    /// 0.
+    ///
+    /// # Arguements
+    ///
+    /// * `value` - The root of the scene, with its entities as
+    ///   "children".
+    pub fn from_value(value: &Value) -> Result<Entity, EntityError> {
+        let mut next_id = 0;
+        let mut problems = Vec::new();
+        let root = read(value, "$", true, &mut next_id, &mut problems);
+        match root {
+            Some(root) if problems.is_empty() => Ok(root),
+            _ => Err(EntityError::Invalid(problems)),
+        }
+    }
+
+    /// The entity as JSON in the format `from_value` reads. Ids are not
+    /// part of it.
+    pub fn to_value(&self) -> Value {
+        let mut object = Map::new();
+        if let Some(noun) = &self.noun {
+            object.insert("noun".to_string(), json!(noun));
+        }
+        if !self.adjectives.is_empty() {
+            object.insert("adjectives".to_string(), json!(self.adjectives));
+        }
+        if !self.components.is_empty() {
+            object.insert("is".to_string(), json!(self.components));
+        }
+        if let Some(relation) = &self.relation {
+            object.insert("relation".to_string(), json!(relation));
+        }
+        if let Some(gender) = &self.gender {
+            object.insert("gender".to_string(), json!(gender));
+        }
+        if !self.properties.is_empty() {
+            object.insert(
+                "properties".to_string(),
+                Value::Object(self.properties.clone()),
+            );
+        }
+        if !self.children.is_empty() {
+            let children: Vec<Value> =
+                self.children.iter().map(Entity::to_value).collect();
+            object.insert("children".to_string(), Value::Array(children));
+        }
+        Value::Object(object)
+    }
+
+    /// Whether the entity's noun is `noun`.
+    pub fn is_a(&self, noun: &str) -> bool {
+        self.noun.as_ref().map_or(false, |x| x == noun)
+    }
+
+    /// How the entity sits in its parent, "in" if it was not given.
+    pub fn relation(&self) -> &str {
+        self.relation.as_ref().map_or(IN, |x| x.as_str())
+    }
+
+    /// How many entities this one can hold, read from the "capacity"
+    /// property. None if there is no limit.
+    pub fn capacity(&self) -> Option<usize> {
+        self.properties
+            .get("capacity")
+            .and_then(Value::as_u64)
+            .map(|x| x as usize)
+    }
+
+    /// The largest id of the entity and everything it holds.
+    pub fn max_id(&self) -> usize {
+        self.children
+            .iter()
+            .map(Entity::max_id)
+            .fold(self.id, |max, id| max.max(id))
+    }
+}
+
+fn read(
+    value: &Value,
+    path: &str,
+    is_root: bool,
+    next_id: &mut usize,
+    problems: &mut Vec<Invalid>,
+) -> Option<Entity> {
+    let object = match value {
+        Value::Object(object) => object,
+        _ => {
+            invalid(problems, path, Problem::ExpectedObject);
+            return None;
+        }
+    };
+    let mut entity = Entity::new(*next_id, "");
+    *next_id += 1;
+    for key in object.keys() {
+        if !FIELDS.contains(&key.as_str()) {
+            invalid(problems, path, Problem::UnknownField(key.to_string()));
+        }
+    }
+    entity.noun = read_string(object, path, "noun", problems);
+    if entity.noun.is_none() && !is_root {
+        invalid(problems, path, Problem::MissingNoun);
+    }
+    entity.adjectives = read_strings(object, path, "adjectives", problems);
+    entity.components = read_strings(object, path, "is", problems);
+    entity.relation = read_string(object, path, "relation", problems);
+    if let Some(relation) = &entity.relation {
+        if ![IN, ON, UNDER].contains(&relation.as_str()) {
+            let problem = Problem::UnknownRelation(relation.to_string());
+            invalid(problems, &format!("{}.relation", path), problem);
+        }
+    }
+    entity.gender = read_string(object, path, "gender", problems);
+    match object.get("properties") {
+        Some(Value::Object(properties)) => {
+            entity.properties = properties.clone()
+        }
+        Some(_) => invalid(
+            problems,
+            &format!("{}.properties", path),
+            Problem::ExpectedObject,
+        ),
+        None => (),
+    }
+    match object.get("children") {
+        Some(Value::Array(children)) => {
+            for (index, child) in children.iter().enumerate() {
+                let path = format!("{}.children[{}]", path, index);
+                if let Some(child) =
+                    read(child, &path, false, next_id, problems)
+                {
+                    entity.children.push(child);
+                }
+            }
+        }
+        Some(_) => invalid(
+            problems,
+            &format!("{}.children", path),
+            Problem::ExpectedArray,
+        ),
+        None => (),
+    }
+    Some(entity)
+}
+
+fn read_string(
+    object: &Map<String, Value>,
+    path: &str,
+    field: &str,
+    problems: &mut Vec<Invalid>,
+) -> Option<String> {
+    match object.get(field) {
+        Some(Value::String(string)) => Some(string.to_string()),
+        Some(_) => {
+            let path = format!("{}.{}", path, field);
+            invalid(problems, &path, Problem::ExpectedString);
+            None
+        }
+        None => None,
+    }
+}
+
+fn read_strings(
+    object: &Map<String, Value>,
+    path: &str,
+    field: &str,
+    problems: &mut Vec<Invalid>,
+) -> Vec<String> {
+    let path = format!("{}.{}", path, field);
+    match object.get(field) {
+        Some(Value::Array(values)) => values
+            .iter()
+            .enumerate()
+            .filter_map(|(index, value)| match value {
+                Value::String(string) => Some(string.to_string()),
+                _ => {
+                    let path = format!("{}[{}]", path, index);
+                    invalid(problems, &path, Problem::ExpectedString);
+                    None
+                }
+            })
+            .collect(),
+        Some(_) => {
+            invalid(problems, &path, Problem::ExpectedArray);
+            Vec::new()
+        }
+        None => Vec::new(),
+    }
+}
+
+fn invalid(problems: &mut Vec<Invalid>, path: &str, problem: Problem) {
+    problems.push(Invalid {
+        path: path.to_string(),
+        problem: problem,
+    });
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::ExpectedObject => fmt.write_str("expected an object"),
+            Problem::ExpectedArray => fmt.write_str("expected an array"),
+            Problem::ExpectedString => fmt.write_str("expected a string"),
+            Problem::MissingNoun => fmt.write_str("missing a noun"),
+            Problem::UnknownField(field) => {
+                write!(fmt, "unknown field \"{}\"", field)
+            }
+            Problem::UnknownRelation(relation) => {
+                write!(fmt, "unknown relation \"{}\"", relation)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Invalid {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}: {}", self.path, self.problem)
+    }
+}
+
+impl fmt::Display for EntityError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EntityError::Json(error) => write!(fmt, "Not JSON: {}", error),
+            EntityError::Invalid(problems) => {
+                fmt.write_str("Invalid scene:")?;
+                for problem in problems {
+                    write!(fmt, "\n{}", problem)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for EntityError {}
+
+#[cfg(test)]
+mod test {
+    use super::Entity;
+    use super::EntityError;
+    use super::Invalid;
+    use super::Problem;
+    use std::fs;
+
+    #[test]
+    fn test_reads_scene_json() {
+        let text =
+            fs::read_to_string("rust/test-data/test-scene.json").unwrap();
+        let scene = Entity::from_json(&text).unwrap();
+        assert_eq!(None, scene.noun);
+        assert_eq!(0, scene.id);
+        let table = &scene.children[1];
+        assert!(table.is_a("table"));
+        assert_eq!(2, table.id);
+        assert_eq!("on", table.children[1].relation());
+        assert_eq!(vec!["blotchy", "red"], table.children[1].adjectives);
+        assert_eq!(vec!["edible"], table.children[1].components);
+        assert_eq!(Some("female".to_string()), scene.children[2].gender);
+        assert_eq!(6, scene.max_id());
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&text).unwrap(),
+            scene.to_value()
+        );
+    }
+
+    #[test]
+    fn test_reports_problems_with_paths() {
+        let value = json!({"children": [
+            {"noun": "apple", "adjective": ["red"]},
+            {"noun": "table", "children": [
+                {"adjectives": ["red", 3], "relation": "beside"},
+                "box"
+            ]},
+            {"noun": "rock", "properties": {"weight": 20}, "is": "heavy"}
+        ]});
+        let invalid = |path: &str, problem| Invalid {
+            path: path.to_string(),
+            problem: problem,
+        };
+        assert_eq!(
+            Err(EntityError::Invalid(vec![
+                invalid(
+                    "$.children[0]",
+                    Problem::UnknownField("adjective".to_string())
+                ),
+                invalid("$.children[1].children[0]", Problem::MissingNoun),
+                invalid(
+                    "$.children[1].children[0].adjectives[1]",
+                    Problem::ExpectedString
+                ),
+                invalid(
+                    "$.children[1].children[0].relation",
+                    Problem::UnknownRelation("beside".to_string())
+                ),
+                invalid("$.children[1].children[1]", Problem::ExpectedObject),
+                invalid("$.children[2].is", Problem::ExpectedArray),
+            ])),
+            Entity::from_value(&value)
+        );
+        let error = Entity::from_json("{\"children\": [{}]}").unwrap_err();
+        assert_eq!(
+            "Invalid scene:\n$.children[0]: missing a noun",
+            error.to_string()
+        );
+        match Entity::from_json("{") {
+            Err(EntityError::Json(_)) => (),
+            _ => panic!("should not be JSON"),
+        }
+    }
+}
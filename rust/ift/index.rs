@@ -0,0 +1,249 @@
+use entity::Entity;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// Where an entity sits in a scene.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Location {
+    /// The id of the entity holding it.
+    pub parent: usize,
+    /// Its position among its parent's children.
+    pub position: usize,
+}
+
+/// Finds the entities of a scene by id, noun or adjective without walking
+/// the tree. The root itself is not indexed, only what it holds.
+pub struct Index {
+    root: usize,
+    locations: HashMap<usize, Location>,
+    nouns: HashMap<String, HashSet<usize>>,
+    adjectives: HashMap<String, HashSet<usize>>,
+}
+
+impl Index {
+    /// Indexes everything the root holds.
+    pub fn new(root: &Entity) -> Index {
+        let mut index = Index {
+            root: root.id,
+            locations: HashMap::new(),
+            nouns: HashMap::new(),
+            adjectives: HashMap::new(),
+        };
+        for (position, child) in root.children.iter().enumerate() {
+            index.add(child, root.id, position);
+        }
+        index
+    }
+
+    pub fn location(&self, id: usize) -> Option<Location> {
+        self.locations.get(&id).cloned()
+    }
+
+    /// Whether the entity is the root or something in it.
+    pub fn contains(&self, id: usize) -> bool {
+        id == self.root || self.locations.contains_key(&id)
+    }
+
+    /// The child positions leading from the root to the entity.
+    pub fn path(&self, id: usize) -> Option<Vec<usize>> {
+        let mut path = Vec::new();
+        let mut id = id;
+        while id != self.root {
+            let location = self.locations.get(&id)?;
+            path.push(location.position);
+            id = location.parent;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Whether the entity is held by `ancestor`, directly or through
+    /// what it holds.
+    pub fn is_inside(&self, id: usize, ancestor: usize) -> bool {
+        let mut id = id;
+        while let Some(location) = self.locations.get(&id) {
+            if location.parent == ancestor {
+                return true;
+            }
+            id = location.parent;
+        }
+        false
+    }
+
+    /// The ids of the entities with the noun and every one of the
+    /// adjectives, in no particular order. Any noun matches if none is
+    /// given.
+    ///
+    /// # Arguements
+    ///
+    /// * `noun` - The noun to look for.
+    /// * `adjectives` - The adjectives an entity must all have.
+    pub fn find(
+        &self,
+        noun: Option<&str>,
+        adjectives: &[String],
+    ) -> Vec<usize> {
+        let mut sets: Vec<&HashSet<usize>> = Vec::new();
+        if let Some(noun) = noun {
+            match self.nouns.get(noun) {
+                Some(ids) => sets.push(ids),
+                None => return Vec::new(),
+            }
+        }
+        for adjective in adjectives {
+            match self.adjectives.get(adjective) {
+                Some(ids) => sets.push(ids),
+                None => return Vec::new(),
+            }
+        }
+        // start from the fewest ids to check the others against
+        sets.sort_by_key(|ids| ids.len());
+        match sets.split_first() {
+            Some((first, rest)) => first
+                .iter()
+                .filter(|id| rest.iter().all(|ids| ids.contains(id)))
+                .cloned()
+                .collect(),
+            None => self.locations.keys().cloned().collect(),
+        }
+    }
+
+    /// Indexes an entity placed in the scene, along with everything it
+    /// holds.
+    ///
+    /// # Arguements
+    ///
+    /// * `entity` - The entity, already among its parent's children.
+    /// * `parent` - The id of its parent.
+    /// * `position` - Its position among its parent's children.
+    pub fn add(&mut self, entity: &Entity, parent: usize, position: usize) {
+        self.place(entity.id, parent, position);
+        self.add_words(entity);
+        for (position, child) in entity.children.iter().enumerate() {
+            self.add(child, entity.id, position);
+        }
+    }
+
+    /// Forgets an entity taken out of the scene, along with everything it
+    /// holds.
+    pub fn forget(&mut self, entity: &Entity) {
+        self.locations.remove(&entity.id);
+        self.forget_words(entity);
+        for child in &entity.children {
+            self.forget(child);
+        }
+    }
+
+    /// Records where an entity now is. What it holds moves with it, so
+    /// their locations stay as they are.
+    pub fn place(&mut self, id: usize, parent: usize, position: usize) {
+        let location = Location {
+            parent: parent,
+            position: position,
+        };
+        self.locations.insert(id, location);
+    }
+
+    /// Records the positions of a parent's children from `from` on, after
+    /// the child that was there was taken out.
+    pub fn shift(&mut self, parent: &Entity, from: usize) {
+        for (position, child) in parent.children.iter().enumerate().skip(from) {
+            if let Some(location) = self.locations.get_mut(&child.id) {
+                location.position = position;
+            }
+        }
+    }
+
+    /// Indexes the entity's noun and adjectives, but not its children.
+    pub fn add_words(&mut self, entity: &Entity) {
+        if let Some(noun) = &entity.noun {
+            self.nouns
+                .entry(noun.to_string())
+                .or_default()
+                .insert(entity.id);
+        }
+        for adjective in &entity.adjectives {
+            self.adjectives
+                .entry(adjective.to_string())
+                .or_default()
+                .insert(entity.id);
+        }
+    }
+
+    /// Forgets the entity's noun and adjectives, as before they change.
+    pub fn forget_words(&mut self, entity: &Entity) {
+        if let Some(noun) = &entity.noun {
+            if let Some(ids) = self.nouns.get_mut(noun) {
+                ids.remove(&entity.id);
+            }
+        }
+        for adjective in &entity.adjectives {
+            if let Some(ids) = self.adjectives.get_mut(adjective) {
+                ids.remove(&entity.id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Index;
+    use super::Location;
+    use entity::Entity;
+
+    #[test]
+    fn test_finds_entities_by_words() {
+        let scene = Entity::from_value(&json!({"children": [
+            {"noun": "apple", "adjectives": ["red", "dirty"]},
+            {"noun": "table", "children": [
+                {"noun": "table", "adjectives": ["red"], "relation": "on"},
+                {"noun": "apple", "adjectives": ["clean"]}
+            ]}
+        ]}))
+        .unwrap();
+        let index = Index::new(&scene);
+        let mut apples = index.find(Some("apple"), &[]);
+        apples.sort();
+        assert_eq!(vec![1, 4], apples);
+        assert_eq!(vec![1], index.find(Some("apple"), &["red".to_string()]));
+        let mut red = index.find(None, &["red".to_string()]);
+        red.sort();
+        assert_eq!(vec![1, 3], red);
+        assert!(index.find(Some("pear"), &[]).is_empty());
+        assert!(index.find(Some("apple"), &["green".to_string()]).is_empty());
+        assert_eq!(4, index.find(None, &[]).len());
+    }
+
+    #[test]
+    fn test_locates_entities() {
+        let scene = Entity::from_value(&json!({"children": [
+            {"noun": "apple"},
+            {"noun": "table", "children": [
+                {"noun": "box", "children": [{"noun": "knife"}]}
+            ]}
+        ]}))
+        .unwrap();
+        let mut index = Index::new(&scene);
+        assert_eq!(Some(vec![1, 0, 0]), index.path(4));
+        assert_eq!(Some(vec![]), index.path(0));
+        assert_eq!(None, index.path(9));
+        assert!(index.is_inside(4, 2));
+        assert!(!index.is_inside(2, 4));
+        assert!(!index.contains(9));
+
+        let mut scene = scene;
+        let apple = scene.children.remove(0);
+        index.forget(&apple);
+        index.shift(&scene, 0);
+        assert!(!index.contains(1));
+        assert!(index.find(Some("apple"), &[]).is_empty());
+        assert_eq!(
+            Some(Location {
+                parent: 0,
+                position: 0
+            }),
+            index.location(2)
+        );
+        assert_eq!(Some(vec![0, 0, 0]), index.path(4));
+    }
+}
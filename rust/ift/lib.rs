@@ -1,9 +1,15 @@
-#[macro_use]
-extern crate lazy_static;
-
 #[macro_use]
 extern crate serde_json;
 extern crate cfg;
 
+pub mod answer;
+pub mod conversation;
+pub mod describe;
+pub mod entity;
+pub mod index;
+pub mod predicate;
 pub mod scene;
 pub mod sentence;
+#[cfg(test)]
+mod test_util;
+pub mod verbs;
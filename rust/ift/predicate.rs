@@ -0,0 +1,236 @@
+use entity::Entity;
+use std::collections::HashMap;
+
+/// A test that nodes of the scene either pass or fail. Closures taking an
+/// `&Entity` are predicates, so they may capture whatever state they
+/// need.
+pub trait Predicate {
+    fn test(&self, entity: &Entity) -> bool;
+
+    /// Passes nodes that pass both predicates.
+    fn and<P: Predicate>(self, other: P) -> And<Self, P>
+    where
+        Self: Sized,
+    {
+        And(self, other)
+    }
+
+    /// Passes nodes that pass either predicate.
+    fn or<P: Predicate>(self, other: P) -> Or<Self, P>
+    where
+        Self: Sized,
+    {
+        Or(self, other)
+    }
+
+    /// Passes nodes that fail this predicate.
+    fn not(self) -> Not<Self>
+    where
+        Self: Sized,
+    {
+        Not(self)
+    }
+}
+
+impl<F> Predicate for F
+where
+    F: Fn(&Entity) -> bool,
+{
+    fn test(&self, entity: &Entity) -> bool {
+        self(entity)
+    }
+}
+
+pub struct And<A, B>(A, B);
+
+impl<A: Predicate, B: Predicate> Predicate for And<A, B> {
+    fn test(&self, entity: &Entity) -> bool {
+        self.0.test(entity) && self.1.test(entity)
+    }
+}
+
+pub struct Or<A, B>(A, B);
+
+impl<A: Predicate, B: Predicate> Predicate for Or<A, B> {
+    fn test(&self, entity: &Entity) -> bool {
+        self.0.test(entity) || self.1.test(entity)
+    }
+}
+
+pub struct Not<A>(A);
+
+impl<A: Predicate> Predicate for Not<A> {
+    fn test(&self, entity: &Entity) -> bool {
+        !self.0.test(entity)
+    }
+}
+
+/// Applied by `Scene::select` to every node it selects.
+pub trait Transform {
+    /// May change the node. Returns false to remove it from the scene.
+    fn apply(&self, entity: &mut Entity) -> bool;
+}
+
+impl<F> Transform for F
+where
+    F: Fn(&mut Entity) -> bool,
+{
+    fn apply(&self, entity: &mut Entity) -> bool {
+        self(entity)
+    }
+}
+
+/// Passes nodes with the component.
+pub struct Component(String);
+
+impl Predicate for Component {
+    fn test(&self, entity: &Entity) -> bool {
+        entity.components.contains(&self.0)
+    }
+}
+
+/// Passes nodes described by the adjective.
+pub struct Adjective(String);
+
+impl Predicate for Adjective {
+    fn test(&self, entity: &Entity) -> bool {
+        entity.adjectives.contains(&self.0)
+    }
+}
+
+/// The question types a scene can answer, as "edible" in "is the apple
+/// edible". A type without a registered predicate asks whether the node
+/// has it as a component or an adjective, so only computed qualities need
+/// registering.
+pub struct QuestionTypes {
+    predicates: HashMap<String, Box<dyn Predicate>>,
+}
+
+impl QuestionTypes {
+    /// Registers the "exist" question type.
+    pub fn new() -> QuestionTypes {
+        let mut types = QuestionTypes::empty();
+        types.register("exist", exist);
+        types
+    }
+
+    /// No registered question types, so every type is read as a
+    /// component or adjective.
+    pub fn empty() -> QuestionTypes {
+        QuestionTypes {
+            predicates: HashMap::new(),
+        }
+    }
+
+    /// Adds a question type, replacing any predicate it already had.
+    ///
+    /// # Arguements
+    ///
+    /// * `name` - The type as it appears in the words file.
+    /// * `predicate` - What a node must pass to answer yes.
+    pub fn register<P>(&mut self, name: &str, predicate: P)
+    where
+        P: Predicate + 'static,
+    {
+        self.predicates
+            .insert(name.to_string(), Box::new(predicate));
+    }
+
+    /// The registered predicate of a question type, if any.
+    pub fn get(&self, name: &str) -> Option<&dyn Predicate> {
+        self.predicates.get(name).map(|predicate| &**predicate)
+    }
+
+    /// Whether a node answers yes to a question of the given type.
+    ///
+    /// # Arguements
+    ///
+    /// * `name` - The type of the question.
+    /// * `entity` - The node being asked about.
+    pub fn test(&self, name: &str, entity: &Entity) -> bool {
+        match self.get(name) {
+            Some(predicate) => predicate.test(entity),
+            None => is(name).or(described(name)).test(entity),
+        }
+    }
+}
+
+impl Default for QuestionTypes {
+    fn default() -> QuestionTypes {
+        QuestionTypes::new()
+    }
+}
+
+/// Passes every node.
+pub fn exist(_: &Entity) -> bool {
+    true
+}
+
+/// Passes nodes with the component, as "edible".
+pub fn is(component: &str) -> Component {
+    Component(component.to_string())
+}
+
+/// Passes nodes with the adjective.
+pub fn described(adjective: &str) -> Adjective {
+    Adjective(adjective.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::described;
+    use super::is;
+    use super::Predicate;
+    use super::QuestionTypes;
+    use entity::Entity;
+    use serde_json::Value;
+    use test_util::entity;
+
+    #[test]
+    fn test_predicates_compose() {
+        let apple = entity(json!({"noun": "apple", "is": ["edible"]}));
+        let table = entity(json!({"noun": "table"}));
+        let is_apple = |entity: &Entity| entity.is_a("apple");
+        let edible_apple = is("edible").and(is_apple);
+        assert!(edible_apple.test(&apple));
+        assert!(!edible_apple.test(&table));
+        assert!(is("edible")
+            .or(|entity: &Entity| entity.is_a("table"))
+            .test(&table));
+        assert!(is("edible").not().test(&table));
+        assert!(!is("edible").not().and(is_apple).test(&apple));
+    }
+
+    #[test]
+    fn test_question_types_hold_closures() {
+        let mut types = QuestionTypes::new();
+        assert!(types.get("heavy").is_none());
+        let limit = 10;
+        types.register("heavy", move |entity: &Entity| {
+            entity.properties.get("weight").and_then(Value::as_u64)
+                > Some(limit)
+        });
+        let heavy = types.get("heavy").unwrap();
+        let rock = |weight| {
+            entity(json!({"noun": "rock", "properties": {"weight": weight}}))
+        };
+        assert!(heavy.test(&rock(20)));
+        assert!(!heavy.test(&rock(5)));
+        assert!(types.get("edible").is_none());
+        let apple = entity(json!({"noun": "apple", "is": ["edible"]}));
+        assert!(types.test("edible", &apple));
+    }
+
+    #[test]
+    fn test_types_default_to_components_and_adjectives() {
+        let types = QuestionTypes::new();
+        let apple = entity(
+            json!({"noun": "apple", "adjectives": ["red"], "is": ["poisoned"]}),
+        );
+        assert!(types.test("poisoned", &apple));
+        assert!(types.test("red", &apple));
+        assert!(!types.test("shiny", &apple));
+        assert!(types.test("exist", &apple));
+        assert!(described("red").and(is("poisoned")).test(&apple));
+    }
+}
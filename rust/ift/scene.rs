@@ -1,201 +1,837 @@
+use answer::Answer;
+use answer::Place;
+use entity::Entity;
+use entity::EntityError;
+pub use entity::IN;
+pub use entity::ON;
+pub use entity::UNDER;
+use index::Index;
+use predicate::exist;
+use predicate::Predicate;
+use predicate::QuestionTypes;
+use predicate::Transform;
+use sentence::Modifier;
 use sentence::NounClause;
+use sentence::PrepClause;
+use sentence::Pronoun;
+use sentence::Quantity;
 use sentence::Sentence;
+use sentence::Wh;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+
+/// How many earlier mentions a pronoun may look back through.
+const MAX_MENTIONS: usize = 10;
+
+/// The noun of a clause that stands for whichever node it describes, as
+/// in "the dirty one".
+pub const ONE: &str = "one";
+
+/// Relates nodes that share a parent rather than a node to its parent.
+pub const NEAR: &str = "near";
+
+/// The noun of the node holding what the player carries. It is added to
+/// the root of the scene the first time something is taken.
+pub const INVENTORY: &str = "inventory";
 
 pub struct Scene {
-    root: Value,
+    root: Entity,
+    /// Finds nodes by id, noun and adjective.
+    index: Index,
+    /// The id the next entity added to the scene gets.
+    next_id: usize,
+    /// The ids of the nodes noun clauses were resolved to, most recent
+    /// last.
+    mentioned: Vec<Vec<usize>>,
+    /// What each type of question asks of a node.
+    pub question_types: QuestionTypes,
+}
+
+/// What a noun clause refers to once its count is taken into account,
+/// as the ids of the nodes. The nodes are read with `Scene::get`, so
+/// they are as the scene has them when used.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Resolution {
+    /// Exactly one node was referred to.
+    Unique(usize),
+    /// Several nodes were referred to, as by "all apples" or "2 apples".
+    Many(Vec<usize>),
+    /// "the apple" matched more than one node.
+    Ambiguous(Vec<usize>),
+    /// Fewer nodes matched than the number asked for.
+    Insufficient { wanted: usize, found: Vec<usize> },
+    /// No node matched.
+    NotFound,
+}
+
+/// Why a node could not be moved. The scene is left as it was.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MoveError {
+    /// The node to move is not in the scene.
+    NotFound,
+    /// The node to move it into is not in the scene.
+    ParentNotFound,
+    /// The root is the scene itself and stays where it is.
+    Root,
+    /// A relation other than "in", "on" and "under".
+    UnknownRelation(String),
+    /// The parent is the node itself or something it holds, as a table
+    /// put in the apple on it.
+    InsideItself,
+    /// The parent already holds as many nodes as its capacity.
+    Full { capacity: usize },
+}
+
+impl Resolution {
+    /// Applies a clause's quantity to the nodes that matched it.
+    ///
+    /// # Arguements
+    ///
+    /// * `quantity` - How many nodes the clause refers to.
+    /// * `found` - The ids of every node matching the clause, in scene
+    ///   order.
+    pub fn new(quantity: Quantity, mut found: Vec<usize>) -> Resolution {
+        if found.is_empty() || quantity == Quantity::Number(0) {
+            return Resolution::NotFound;
+        }
+        match quantity {
+            Quantity::Definite | Quantity::Unspecified => {
+                if found.len() == 1 {
+                    Resolution::Unique(found.remove(0))
+                } else {
+                    Resolution::Ambiguous(found)
+                }
+            }
+            Quantity::Indefinite | Quantity::Number(1) => {
+                Resolution::Unique(found.remove(0))
+            }
+            Quantity::Number(wanted) => {
+                if found.len() < wanted {
+                    Resolution::Insufficient {
+                        wanted: wanted,
+                        found: found,
+                    }
+                } else {
+                    found.truncate(wanted);
+                    Resolution::Many(found)
+                }
+            }
+            Quantity::All => Resolution::Many(found),
+        }
+    }
+
+    /// Leaves out the given nodes, as "the red apple" is left out of
+    /// "take all apples except the red apple".
+    ///
+    /// # Arguements
+    ///
+    /// * `excluded` - The ids of the nodes to leave out.
+    pub fn without(self, excluded: &[usize]) -> Resolution {
+        let keep = |nodes: Vec<usize>| -> Vec<usize> {
+            nodes.into_iter().filter(|x| !excluded.contains(x)).collect()
+        };
+        match self {
+            Resolution::Unique(node) => {
+                if excluded.contains(&node) {
+                    Resolution::NotFound
+                } else {
+                    Resolution::Unique(node)
+                }
+            }
+            Resolution::Many(nodes) => {
+                let nodes = keep(nodes);
+                if nodes.is_empty() {
+                    Resolution::NotFound
+                } else {
+                    Resolution::Many(nodes)
+                }
+            }
+            Resolution::Ambiguous(candidates) => {
+                let mut candidates = keep(candidates);
+                match candidates.len() {
+                    0 => Resolution::NotFound,
+                    1 => Resolution::Unique(candidates.remove(0)),
+                    _ => Resolution::Ambiguous(candidates),
+                }
+            }
+            Resolution::Insufficient { wanted, found } => {
+                Resolution::Insufficient {
+                    wanted: wanted,
+                    found: keep(found),
+                }
+            }
+            Resolution::NotFound => Resolution::NotFound,
+        }
+    }
+
+    /// The ids of the nodes referred to, if the resolution settled on
+    /// any.
+    pub fn ids(&self) -> Vec<usize> {
+        match self {
+            Resolution::Unique(node) => vec![*node],
+            Resolution::Many(nodes) => nodes.clone(),
+            _ => Vec::new(),
+        }
+    }
 }
 
 impl Scene {
-    pub fn new(root: Value) -> Scene {
-        Scene { root: root }
+    pub fn new(root: Entity) -> Scene {
+        Scene {
+            index: Index::new(&root),
+            next_id: root.max_id() + 1,
+            root: root,
+            mentioned: Vec::new(),
+            question_types: QuestionTypes::new(),
+        }
+    }
+
+    /// Reads a scene from its JSON value, as `Entity::from_value` does.
+    pub fn from_value(value: &Value) -> Result<Scene, EntityError> {
+        Ok(Scene::new(Entity::from_value(value)?))
+    }
+
+    /// The root of the scene, holding everything in it.
+    pub fn root(&self) -> &Entity {
+        &self.root
     }
 
+    /// The node with the id, if it is in the scene.
+    ///
+    /// # Arguements
+    ///
+    /// * `id` - The id of the node, as returned by `select`.
+    pub fn get(&self, id: usize) -> Option<&Entity> {
+        let path = self.index.path(id)?;
+        Some(path.iter().fold(&self.root, |node, i| &node.children[*i]))
+    }
+
+    /// Copies of the nodes with the ids that are in the scene, for
+    /// answers and questions to the player.
+    ///
+    /// # Arguements
+    ///
+    /// * `ids` - The ids of the nodes, as held by a `Resolution`.
+    pub fn entities(&self, ids: &[usize]) -> Vec<Entity> {
+        ids.iter().filter_map(|id| self.get(*id)).cloned().collect()
+    }
+
+    /// Answers a yes or no question. A question about one of several
+    /// nodes, as "is the apple edible" among many apples, is answered no.
     pub fn ask_question(&mut self, sentence: &Sentence) -> bool {
         let result = self.ask_question_helper(sentence);
         match result {
-            Some(result) => result,
-            None => false,
+            Some(Answer::YesNo(result)) => result,
+            _ => false,
         }
     }
 
-    fn ask_question_helper(&mut self, sentence: &Sentence) -> Option<bool> {
+    fn ask_question_helper(&mut self, sentence: &Sentence) -> Option<Answer> {
         let qtype = &sentence.q_type.to_owned()?;
 
-        let unfiltered = vec![];
+        let subject = sentence.subject();
+        let mut subjects = self.asked_about(sentence);
+        let types = &self.question_types;
+        let passes = |id: &usize| {
+            self.get(*id).map_or(false, |x| types.test(qtype, x))
+        };
+        if subject.pronoun.is_some() {
+            // every node a pronoun stands for must answer yes
+            if !subjects.iter().all(passes) {
+                return Some(Answer::YesNo(false));
+            }
+        } else {
+            match subject.quantity {
+                // "the apple" must be one apple before it is tested
+                Quantity::Definite | Quantity::Unspecified => {
+                    if subjects.len() > 1 {
+                        let candidates = self.entities(&subjects);
+                        return Some(Answer::Ambiguous(candidates));
+                    }
+                }
+                _ => {}
+            }
+            subjects.retain(passes);
+        }
+        let resolution = Resolution::new(subject.quantity, subjects);
+        let found = match resolution {
+            Resolution::Unique(_) | Resolution::Many(_) => true,
+            _ => false,
+        };
+        self.mention(&resolution);
+        return Some(Answer::YesNo(found));
+    }
 
-        let mut location = match &sentence.prep {
-            Some(prep) => Some(self.select(&prep.noun_clause, &unfiltered, None)),
-            None => None,
+    /// Answers a question, open or not. Yes or no questions are answered
+    /// as by `ask_question`, unless their subject could be several nodes,
+    /// and what is found is remembered for later pronouns.
+    pub fn answer(&mut self, sentence: &Sentence) -> Answer {
+        let wh = match sentence.wh {
+            Some(wh) => wh,
+            None => {
+                return self
+                    .ask_question_helper(sentence)
+                    .unwrap_or(Answer::YesNo(false))
+            }
         };
-        if location.is_none() && sentence.prep.is_some() {
-            return Some(false);
+        let asked_about = self.asked_about(sentence);
+        let mut found = self.entities(&asked_about);
+        if let Some(qtype) = &sentence.q_type {
+            let types = &self.question_types;
+            found.retain(|x| types.test(qtype, x));
         }
-        let location: &mut Value = match &mut location {
-            Some(matches) => {
-                if matches.len() == 0 {
-                    None
-                } else {
-                    Some(&mut matches[0])
-                }
+        self.remember(found.iter().map(|x| x.id).collect());
+        match wh {
+            Wh::What | Wh::Which => Answer::Entities(found),
+            Wh::Where => Answer::Locations(
+                found
+                    .into_iter()
+                    .map(|node| {
+                        let places = self.places(node.id);
+                        (node, places)
+                    })
+                    .collect(),
+            ),
+            Wh::HowMany => Answer::Count(found.len()),
+        }
+    }
+
+    /// Every node the subject of a question could be, kept to those
+    /// related to the prepositional clause if it has one.
+    fn asked_about(&mut self, sentence: &Sentence) -> Vec<usize> {
+        let mut subjects = self.candidates(sentence.subject());
+        if let Some(prep) = &sentence.prep {
+            let related = self.related_to(prep);
+            subjects.retain(|x| related.contains(x));
+        }
+        subjects
+    }
+
+    /// The nodes holding a node, innermost first, each with how it
+    /// holds the node inside it.
+    ///
+    /// # Arguements
+    ///
+    /// * `id` - The id of the node to locate.
+    pub fn places(&self, id: usize) -> Vec<Place> {
+        let mut places = Vec::new();
+        let mut child = match self.get(id) {
+            Some(child) => child,
+            None => return places,
+        };
+        while let Some(location) = self.index.location(child.id) {
+            // the root is the scene itself rather than a place in it
+            if location.parent == self.root.id {
+                break;
             }
-            None => None,
+            let parent = match self.get(location.parent) {
+                Some(parent) => parent,
+                None => break,
+            };
+            places.push(Place {
+                relation: child.relation().to_string(),
+                node: parent.clone(),
+            });
+            child = parent;
         }
-        .unwrap_or(&mut self.root);
-        let subject = &sentence.subject;
-        let filters = vec![FILTER_MAP.get(qtype)?];
-        let subjects = Scene::select_custom_root(subject, &filters, None, location);
-        return Some(subjects.len() >= 1);
+        places
     }
 
-    pub fn select(
+    /// Finds the nodes a noun clause refers to, honoring its article or
+    /// number. Pronouns refer to the last compatible mention, and what
+    /// is found is remembered for later pronouns.
+    pub fn resolve(&mut self, noun_clause: &NounClause) -> Resolution {
+        let resolution = self.lookup(noun_clause, None);
+        self.mention(&resolution);
+        resolution
+    }
+
+    /// Resolves every subject of a sentence in order, leaving out what
+    /// its exceptions refer to. Everything found is remembered as one
+    /// mention, so "them" can refer to "the apple and the knife".
+    ///
+    /// # Arguements
+    ///
+    /// * `sentence` - The sentence whose subjects to resolve.
+    /// * `location` - Where the subjects are, unless the verb takes the
+    ///   prepositional clause as its object.
+    pub fn resolve_subjects(
+        &mut self,
+        sentence: &Sentence,
+        location: Option<&PrepClause>,
+    ) -> Vec<Resolution> {
+        let mut excluded = Vec::new();
+        for exception in &sentence.exceptions {
+            excluded.extend(self.candidates(exception));
+        }
+        let related = location.map(|prep| self.related_to(prep));
+        let resolutions: Vec<Resolution> = sentence
+            .subjects
+            .iter()
+            .map(|subject| {
+                self.lookup(subject, related.as_ref()).without(&excluded)
+            })
+            .collect();
+        let found: Vec<usize> =
+            resolutions.iter().flat_map(|x| x.ids()).collect();
+        self.remember(found);
+        resolutions
+    }
+
+    /// Finds what a noun clause refers to as `resolve` does, but without
+    /// remembering it.
+    ///
+    /// # Arguements
+    ///
+    /// * `noun_clause` - The noun clause to look up.
+    /// * `location` - Where what it refers to is, if anywhere.
+    pub fn find(
         &mut self,
         noun_clause: &NounClause,
-        filters: &Vec<&fn(&Value) -> bool>,
-	transform: Option<&fn(&Value) -> bool>,
-    ) -> Vec<Value> {
-        let mut results = Vec::new();
-        Scene::select_helper(
-            &mut results,
-            noun_clause,
-            filters,
-	    transform,
-            &mut self.root,
-        );
-        return results;
+        location: Option<&PrepClause>,
+    ) -> Resolution {
+        let related = location.map(|prep| self.related_to(prep));
+        self.lookup(noun_clause, related.as_ref())
     }
 
-    fn select_custom_root(
+    /// Resolves a noun clause among the nodes in `related`, or anywhere
+    /// if it is None. Pronouns refer to their last mention wherever it is.
+    fn lookup(
+        &mut self,
         noun_clause: &NounClause,
-        filters: &Vec<&fn(&Value) -> bool>,
-	transform: Option<&fn(&Value) -> bool>,
-        root: &mut Value,
-    ) -> Vec<Value> {
+        related: Option<&HashSet<usize>>,
+    ) -> Resolution {
+        match noun_clause.pronoun {
+            Some(pronoun) => self.referent(pronoun),
+            None => {
+                let mut found = self.candidates(noun_clause);
+                if let Some(related) = related {
+                    found.retain(|x| related.contains(x));
+                }
+                Resolution::new(noun_clause.quantity, found)
+            }
+        }
+    }
+
+    /// Remembers what a resolution referred to so that pronouns can
+    /// refer back to it. Only unique and many resolutions are kept.
+    ///
+    /// # Arguements
+    ///
+    /// * `resolution` - What a noun clause was resolved to.
+    pub fn mention(&mut self, resolution: &Resolution) {
+        match resolution {
+            Resolution::Unique(_) | Resolution::Many(_) => {
+                self.remember(resolution.ids())
+            }
+            _ => (),
+        }
+    }
+
+    fn remember(&mut self, ids: Vec<usize>) {
+        if ids.is_empty() {
+            return;
+        }
+        self.mentioned.push(ids);
+        if self.mentioned.len() > MAX_MENTIONS {
+            self.mentioned.remove(0);
+        }
+    }
+
+    /// Finds the last mention a pronoun can refer to. "it" needs a single
+    /// node without a gender, "him" and "her" a single node of that
+    /// gender, and "them" several nodes. Nodes no longer in the scene
+    /// are left out.
+    ///
+    /// # Arguements
+    ///
+    /// * `pronoun` - The pronoun to find a referent for.
+    pub fn referent(&self, pronoun: Pronoun) -> Resolution {
+        for ids in self.mentioned.iter().rev() {
+            let nodes: Vec<&Entity> =
+                ids.iter().filter_map(|id| self.get(*id)).collect();
+            let compatible = match pronoun {
+                Pronoun::Them => nodes.len() > 1,
+                Pronoun::It => nodes.len() == 1 && nodes[0].gender.is_none(),
+                Pronoun::Him => {
+                    nodes.len() == 1 && is_gender(&nodes[0], "male")
+                }
+                Pronoun::Her => {
+                    nodes.len() == 1 && is_gender(&nodes[0], "female")
+                }
+            };
+            if compatible {
+                let ids = nodes.iter().map(|x| x.id).collect();
+                return match pronoun {
+                    Pronoun::Them => Resolution::Many(ids),
+                    _ => Resolution::Unique(nodes[0].id),
+                };
+            }
+        }
+        Resolution::NotFound
+    }
+
+    fn referents(&self, pronoun: Pronoun) -> Vec<usize> {
+        self.referent(pronoun).ids()
+    }
+
+    /// The nodes standing in a spatial relation to a location. "on",
+    /// "in" and "under" give the children of the location with that
+    /// relation, along with everything they hold. "near" gives the other
+    /// children of the location's parent.
+    ///
+    /// # Arguements
+    ///
+    /// * `prep` - The preposition, as "on" in "the apple on the table".
+    /// * `location` - The id of the node the preposition relates to.
+    pub fn related(&self, prep: &str, location: usize) -> Vec<usize> {
         let mut results = Vec::new();
-        Scene::select_helper(&mut results, noun_clause, filters, transform, root);
-        return results;
+        if prep == NEAR {
+            let parent = self
+                .index
+                .location(location)
+                .and_then(|x| self.get(x.parent));
+            if let Some(parent) = parent {
+                results.extend(
+                    parent
+                        .children
+                        .iter()
+                        .map(|x| x.id)
+                        .filter(|x| *x != location),
+                );
+            }
+            return results;
+        }
+        if let Some(location) = self.get(location) {
+            for child in &location.children {
+                if child.relation() == prep {
+                    results.push(child.id);
+                    descendants(child, &mut results);
+                }
+            }
+        }
+        results
     }
 
-    fn select_helper(
-        results: &mut Vec<Value>,
+    /// Every node in a spatial relation to what a prepositional clause
+    /// could refer to, as every apple on any table for "on a table".
+    fn related_to(&mut self, prep: &PrepClause) -> HashSet<usize> {
+        let locations = self.candidates(&prep.noun_clause);
+        locations
+            .iter()
+            .flat_map(|location| self.related(&prep.prep, *location))
+            .collect()
+    }
+
+    /// The nodes among `ids` that a noun clause, with its modifiers,
+    /// could refer to, as the dirty apples among the apples for "the
+    /// one that is dirty". The noun "one" stands for any noun.
+    ///
+    /// # Arguements
+    ///
+    /// * `noun_clause` - The noun clause narrowing down the nodes.
+    /// * `ids` - The ids of the nodes to choose among.
+    pub fn narrow(
+        &mut self,
         noun_clause: &NounClause,
-        filters: &Vec<&fn(&Value) -> bool>,
-	transform: Option<&fn(&Value) -> bool>,
-        value: &mut Value,
-    ) {
-	let mut childs: Vec<Value> = Vec::new();
-        // check the children
-        match &mut value["children"] {
-            Value::Array(children) => {
-                for child in children {
-		    let mut pushChild = true;
-		    if noun_clause.matches(child) {
-			// if we fail a filter we don't want to check children
-			// or add this item to the results
-			let mut ok = true;
-			for filter in filters {
-			    if !filter(child) {
-				ok = false;
-				break;
-			    }
-			}
-			if !ok {
-			    if transform.is_some() {
-				print!("!!{}\n\n", child);
-				childs.push(child.clone());
-			    }
-			    continue;
-			}
-			// match found, but still check children
-			results.push(child.clone());
-			match transform {
-			    Some(transform) => {
-				if transform(child) {
-				    print!("pushy!!!\n");
-				} else {
-				    pushChild = false;
-				}
-			    },
-			    None => (),
-			}
-		    }
-                    Scene::select_helper(results, noun_clause, filters, transform, child);
-		    if pushChild {
-			print!("!!{}\n\n", child);
-			childs.push(child.clone());
-		    }
+        ids: &[usize],
+    ) -> Vec<usize> {
+        let mut noun_clause = noun_clause.clone();
+        if noun_clause.noun == ONE {
+            noun_clause.universal = true;
+        }
+        let found = self.candidates(&noun_clause);
+        ids.iter().cloned().filter(|x| found.contains(x)).collect()
+    }
+
+    /// Every node a noun clause could refer to, reading a pronoun as what
+    /// it was last used for. Modifiers are resolved innermost first, so
+    /// "the apple on the table in the kitchen" finds the kitchen, then the
+    /// tables in it, then the apples on those.
+    fn candidates(&mut self, noun_clause: &NounClause) -> Vec<usize> {
+        let mut found = match noun_clause.pronoun {
+            Some(pronoun) => self.referents(pronoun),
+            None => self.select(noun_clause, &exist, None),
+        };
+        if noun_clause.universal {
+            // "everything" means the things in the scene, not the
+            // inventory itself
+            found.retain(|x| {
+                !self.get(*x).map_or(false, |x| x.is_a(INVENTORY))
+            });
+        }
+        for modifier in &noun_clause.modifiers {
+            match modifier {
+                Modifier::Prep(prep) => {
+                    let related = self.related_to(prep);
+                    found.retain(|x| related.contains(x));
+                }
+                Modifier::Quality(quality) => {
+                    let types = &self.question_types;
+                    found.retain(|x| {
+                        self.get(*x).map_or(false, |x| types.test(quality, x))
+                    });
+                }
+            }
+        }
+        found
+    }
+
+    /// Takes a node out of the scene, along with everything it holds.
+    ///
+    /// # Arguements
+    ///
+    /// * `id` - The id of the node to remove, as found by `select`.
+    pub fn remove(&mut self, id: usize) -> Option<Entity> {
+        let removed = self.detach(id)?;
+        self.index.forget(&removed);
+        Some(removed)
+    }
+
+    /// Moves a node, with everything it holds, into the children of
+    /// `parent`, or to the root of the scene if there is no parent, and
+    /// returns it as it now is. Everything is checked before the node is
+    /// taken out, so the scene is unchanged if the move fails.
+    ///
+    /// # Arguements
+    ///
+    /// * `id` - The id of the node to move.
+    /// * `parent` - The id of the node to move it into.
+    /// * `relation` - Where the node goes relative to its parent, "in" if
+    ///   not given.
+    pub fn move_node(
+        &mut self,
+        id: usize,
+        parent: Option<usize>,
+        relation: Option<&str>,
+    ) -> Result<&Entity, MoveError> {
+        if let Some(relation) = relation {
+            if ![IN, ON, UNDER].contains(&relation) {
+                return Err(MoveError::UnknownRelation(relation.to_string()));
+            }
+        }
+        let parent = parent.unwrap_or(self.root.id);
+        if id == self.root.id {
+            return Err(MoveError::Root);
+        }
+        if !self.index.contains(id) {
+            return Err(MoveError::NotFound);
+        }
+        let holder = self.get(parent).ok_or(MoveError::ParentNotFound)?;
+        if id == parent || self.index.is_inside(parent, id) {
+            return Err(MoveError::InsideItself);
+        }
+        if let Some(capacity) = holder.capacity() {
+            // moving within the same parent takes no more room
+            let held = holder.children.iter().filter(|x| x.id != id).count();
+            if held >= capacity {
+                return Err(MoveError::Full { capacity: capacity });
+            }
+        }
+        let mut moved = self.detach(id).ok_or(MoveError::NotFound)?;
+        moved.relation = relation.map(|x| x.to_string());
+        // the parent was checked to be outside the node, so it is still
+        // in the scene
+        self.attach(moved, parent).ok_or(MoveError::ParentNotFound)
+    }
+
+    /// Takes a node out of its parent's children. It stays indexed, to be
+    /// attached elsewhere or forgotten.
+    fn detach(&mut self, id: usize) -> Option<Entity> {
+        let location = self.index.location(id)?;
+        let path = self.index.path(location.parent)?;
+        let parent = at_path(&mut self.root, &path)?;
+        if location.position >= parent.children.len() {
+            return None;
+        }
+        let detached = parent.children.remove(location.position);
+        self.index.shift(parent, location.position);
+        Some(detached)
+    }
+
+    /// Adds a node to the end of a parent's children.
+    fn attach(&mut self, node: Entity, parent: usize) -> Option<&Entity> {
+        let path = self.index.path(parent)?;
+        let parent = at_path(&mut self.root, &path)?;
+        self.index.place(node.id, parent.id, parent.children.len());
+        parent.children.push(node);
+        parent.children.last()
+    }
+
+    /// Changes a node in place, keeping it findable by its new noun and
+    /// adjectives. Its children are added and taken out with `move_node`
+    /// and `remove` rather than here.
+    ///
+    /// # Arguements
+    ///
+    /// * `id` - The id of the node to change.
+    /// * `change` - Called with the node in the scene.
+    pub fn update<F>(&mut self, id: usize, change: F) -> Option<&Entity>
+    where
+        F: FnOnce(&mut Entity),
+    {
+        let path = self.index.path(id)?;
+        let found = at_path(&mut self.root, &path)?;
+        self.index.forget_words(found);
+        change(found);
+        self.index.add_words(found);
+        Some(found)
+    }
+
+    /// Whether the node is in the player's inventory.
+    pub fn is_held(&self, id: usize) -> bool {
+        match self.inventory() {
+            Some(inventory) => self.index.is_inside(id, inventory),
+            None => false,
+        }
+    }
+
+    /// Moves a node into the player's inventory, as `move_node` does, and
+    /// returns it as it now is. The inventory is only added once the node
+    /// is known to fit in it.
+    ///
+    /// # Arguements
+    ///
+    /// * `id` - The id of the node to take.
+    pub fn hold(&mut self, id: usize) -> Result<&Entity, MoveError> {
+        if id == self.root.id {
+            return Err(MoveError::Root);
+        }
+        if !self.index.contains(id) {
+            return Err(MoveError::NotFound);
+        }
+        let inventory = match self.inventory() {
+            Some(inventory) => inventory,
+            None => {
+                let inventory = Entity::new(self.next_id, INVENTORY);
+                self.next_id += 1;
+                let root = self.root.id;
+                self.index.add(&inventory, root, self.root.children.len());
+                self.root.children.push(inventory);
+                self.next_id - 1
+            }
+        };
+        self.move_node(id, Some(inventory), None)
+    }
+
+    /// The id of the node holding what the player carries.
+    fn inventory(&self) -> Option<usize> {
+        let root = self.root.id;
+        self.index
+            .find(Some(INVENTORY), &[])
+            .into_iter()
+            .find(|x| {
+                self.index.location(*x).map_or(false, |x| x.parent == root)
+            })
+    }
+
+    /// The ids of the nodes a noun clause's noun and adjectives match
+    /// that pass the filter, in scene order. The transform, if given, is
+    /// then applied to each in place, and those it returns false for are
+    /// removed from the scene.
+    ///
+    /// # Arguements
+    ///
+    /// * `noun_clause` - The noun and adjectives to look for.
+    /// * `filter` - What a node must pass to be selected.
+    /// * `transform` - What to do to every selected node.
+    pub fn select(
+        &mut self,
+        noun_clause: &NounClause,
+        filter: &dyn Predicate,
+        transform: Option<&dyn Transform>,
+    ) -> Vec<usize> {
+        let noun = if noun_clause.universal {
+            None
+        } else {
+            Some(noun_clause.noun.as_str())
+        };
+        let mut ids = self.index.find(noun, &noun_clause.adjectives);
+        ids.retain(|x| self.get(*x).map_or(false, |x| filter.test(x)));
+        ids.sort_by_cached_key(|x| self.index.path(*x));
+        if let Some(transform) = transform {
+            for id in &ids {
+                let mut keep = true;
+                self.update(*id, |node| keep = transform.apply(node));
+                if !keep {
+                    self.remove(*id);
                 }
             }
-            _ => (),
         }
-	if transform.is_some() && value["children"] != Value::Null {
-	    value["children"] = Value::Array(childs);
-	}
+        ids
     }
 }
 
-fn remove(_: &Value) -> bool {
-    false
+fn at_path<'a>(tree: &'a mut Entity, path: &[usize]) -> Option<&'a mut Entity> {
+    match path.split_first() {
+        Some((index, rest)) => at_path(tree.children.get_mut(*index)?, rest),
+        None => Some(tree),
+    }
 }
 
-fn exist(_: &Value) -> bool {
-    true
+fn descendants(node: &Entity, results: &mut Vec<usize>) {
+    for child in &node.children {
+        results.push(child.id);
+        descendants(child, results);
+    }
 }
 
-fn edible(item: &Value) -> bool {
-    match &item["is"] {
-        Value::Array(array) => {
-            for item in array {
-                match item {
-                    Value::String(component) => {
-                        if component == "edible" {
-                            return true;
-                        }
-                    }
-                    _ => (),
-                }
+fn is_gender(entity: &Entity, gender: &str) -> bool {
+    entity.gender.as_ref().map_or(false, |x| x == gender)
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MoveError::NotFound => {
+                fmt.write_str("the node is not in the scene")
+            }
+            MoveError::ParentNotFound => {
+                fmt.write_str("the parent is not in the scene")
+            }
+            MoveError::Root => fmt.write_str("the root can't be moved"),
+            MoveError::UnknownRelation(relation) => {
+                write!(fmt, "unknown relation \"{}\"", relation)
+            }
+            MoveError::InsideItself => {
+                fmt.write_str("the parent is inside the node")
+            }
+            MoveError::Full { capacity } => {
+                write!(fmt, "the parent holds {} already", capacity)
             }
         }
-        _ => (),
-    };
-    return false;
+    }
 }
 
-lazy_static! {
-    static ref FILTER_MAP: HashMap<String, fn(&Value) -> bool> = {
-        let mut map = HashMap::new();
-        map.insert("exist".to_string(), exist as fn(&Value) -> bool);
-        map.insert("edible".to_string(), edible as fn(&Value) -> bool);
-        map
-    };
-}
+impl Error for MoveError {}
 
 #[cfg(test)]
 mod test {
+    use super::MoveError;
+    use super::Resolution;
+    use answer::Answer;
+    use predicate::exist;
+    use predicate::is;
+    use predicate::Predicate;
     use super::Scene;
-    use cfg::lang::Lang;
+    use entity::Entity;
     use sentence::NounClause;
+    use sentence::Pronoun;
+    use sentence::Quantity;
     use sentence::Sentence;
     use serde_json::Value;
-    use std::fs;
-
-    fn test_scene() -> Value {
-        let scene =
-            fs::read_to_string("rust/test-data/test-scene.json").unwrap();
-        serde_json::from_str(&scene).unwrap()
-    }
+    use test_util::make_lang;
+    use test_util::test_scene;
+    use test_util::test_sentence;
 
-    fn make_lang() -> Lang {
-        Lang::from_file(
-            "rust/test-data/test-lang-rules.txt",
-            "rust/test-data/test-lang-words.txt",
-        )
-    }
-
-    fn test_sentence(sentence: &str) -> Sentence {
-        let lang = make_lang();
-        Sentence::from_lang(&lang, sentence).unwrap()
+    /// The entity a resolution settled on, as JSON.
+    fn unique(resolution: Resolution, scene: &Scene) -> Value {
+        match resolution {
+            Resolution::Unique(id) => scene.get(id).unwrap().to_value(),
+            resolution => panic!("{:?} is not unique", resolution),
+        }
     }
 
     #[test]
@@ -207,14 +843,416 @@ mod test {
         assert_eq!(true, result);
     }
 
+    #[test]
+    fn test_ask_exist_with_synonym() {
+        let data = test_scene();
+        let mut scene = Scene::new(data);
+        let sentence = test_sentence("does a red fruit exist");
+        let result = scene.ask_question(&sentence);
+        assert_eq!(true, result);
+    }
+
+    fn resolve(scene: &mut Scene, sentence: &str) -> Resolution {
+        let sentence = test_sentence(sentence);
+        scene.resolve(sentence.subject())
+    }
+
+    #[test]
+    fn test_answer_wh_questions() {
+        let mut scene = Scene::new(test_scene());
+        match scene.answer(&test_sentence("what is on the table")) {
+            Answer::Entities(found) => assert_eq!(3, found.len()),
+            _ => panic!("should list what is on the table"),
+        }
+        let sentence = test_sentence("how many apples are there");
+        assert_eq!(Answer::Count(3), scene.answer(&sentence));
+        let sentence = test_sentence("how many apples are on the table");
+        assert_eq!(Answer::Count(2), scene.answer(&sentence));
+        let sentence = test_sentence("is the clean apple edible");
+        assert_eq!(Answer::YesNo(true), scene.answer(&sentence));
+        match scene.answer(&test_sentence("is the apple edible")) {
+            Answer::Ambiguous(found) => assert_eq!(3, found.len()),
+            _ => panic!("any of the three apples could be meant"),
+        }
+        assert!(!scene.ask_question(&test_sentence("is the apple edible")));
+        let clean = json!({
+            "noun": "apple",
+            "adjectives": ["clean"],
+            "is": ["edible"],
+            "relation": "on"
+        });
+        match scene.answer(&test_sentence("where is the clean apple")) {
+            Answer::Locations(found) => {
+                assert_eq!(1, found.len());
+                assert_eq!(clean, found[0].0.to_value());
+                assert_eq!(1, found[0].1.len());
+                assert_eq!("on", found[0].1[0].relation);
+                assert!(found[0].1[0].node.is_a("table"));
+            }
+            _ => panic!("should locate the clean apple"),
+        }
+        // the answer is what later pronouns refer to
+        let it = scene.resolve(&NounClause::from_pronoun("it".to_string()));
+        assert_eq!(clean, unique(it, &scene));
+        assert!(scene.places(100).is_empty());
+    }
+
+    #[test]
+    fn test_resolve_subjects_with_exceptions() {
+        let mut scene = Scene::new(test_scene());
+        let sentence = test_sentence(
+            "take all apples and the ghost except the dirty apple",
+        );
+        let resolutions = scene.resolve_subjects(&sentence, None);
+        match &resolutions[..] {
+            [Resolution::Many(apples), Resolution::Unique(ghost)] => {
+                let apples = scene.entities(apples);
+                assert_eq!(2, apples.len());
+                let dirty = vec!["red", "dirty"];
+                assert!(apples.iter().all(|x| x.adjectives != dirty));
+                assert!(scene.get(*ghost).unwrap().is_a("ghost"));
+            }
+            _ => panic!("should leave out the dirty apple"),
+        }
+        assert_eq!(3, scene.referents(Pronoun::Them).len());
+
+        let sentence = test_sentence(
+            "take all apples except the dirty apple and the clean apple",
+        );
+        match &scene.resolve_subjects(&sentence, None)[..] {
+            [Resolution::Many(apples)] => assert_eq!(1, apples.len()),
+            _ => panic!("should leave out both apples"),
+        }
+
+        let sentence = test_sentence("take the red apple but the dirty apple");
+        match &scene.resolve_subjects(&sentence, None)[..] {
+            [Resolution::Unique(apple)] => {
+                let apple = scene.get(*apple).unwrap();
+                assert_eq!(vec!["blotchy", "red"], apple.adjectives);
+            }
+            _ => panic!("should leave only the blotchy apple"),
+        }
+
+        let sentence = test_sentence("take everything except the table");
+        let everything = scene.resolve_subjects(&sentence, None);
+        match &everything[..] {
+            [Resolution::Many(found)] => {
+                let found = scene.entities(found);
+                assert!(found.iter().all(|x| !x.is_a("table")));
+                assert!(found.iter().any(|x| x.is_a("ghost")));
+            }
+            _ => panic!("should find everything but the tables"),
+        }
+    }
+
+    #[test]
+    fn test_resolve_definite() {
+        let mut scene = Scene::new(test_scene());
+        assert_eq!(
+            json!({
+                "noun": "apple",
+                "adjectives": ["red", "dirty"],
+                "is": ["edible"]
+            }),
+            unique(resolve(&mut scene, "eat the dirty apple"), &scene)
+        );
+        match resolve(&mut scene, "eat the red apple") {
+            Resolution::Ambiguous(candidates) => {
+                assert_eq!(2, candidates.len())
+            }
+            _ => panic!("should be ambiguous"),
+        }
+        assert_eq!(
+            Resolution::NotFound,
+            resolve(&mut scene, "eat the green apple")
+        );
+    }
+
+    #[test]
+    fn test_resolve_indefinite_picks_one() {
+        let mut scene = Scene::new(test_scene());
+        assert_eq!(
+            json!({
+                "noun": "apple",
+                "adjectives": ["red", "dirty"],
+                "is": ["edible"]
+            }),
+            unique(resolve(&mut scene, "eat a red apple"), &scene)
+        );
+    }
+
+    #[test]
+    fn test_resolve_numbers_and_all() {
+        let mut scene = Scene::new(test_scene());
+        match resolve(&mut scene, "eat 2 red apples") {
+            Resolution::Many(found) => assert_eq!(2, found.len()),
+            _ => panic!("should find two apples"),
+        }
+        match resolve(&mut scene, "eat three red apples") {
+            Resolution::Insufficient { wanted, found } => {
+                assert_eq!(3, wanted);
+                assert_eq!(2, found.len());
+            }
+            _ => panic!("should be insufficient"),
+        }
+        match resolve(&mut scene, "eat all apples") {
+            Resolution::Many(found) => assert_eq!(3, found.len()),
+            _ => panic!("should find every apple"),
+        }
+        match resolve(&mut scene, "eat all fruits") {
+            Resolution::Many(found) => assert_eq!(3, found.len()),
+            _ => panic!("should find every apple by its alias"),
+        }
+        match resolve(&mut scene, "eat the red apples") {
+            Resolution::Many(found) => assert_eq!(2, found.len()),
+            _ => panic!("should find every red apple"),
+        }
+        assert_eq!(
+            Resolution::NotFound,
+            Resolution::new(Quantity::Number(0), vec![1])
+        );
+    }
+
+    #[test]
+    fn test_pronouns_refer_to_last_mention() {
+        let mut scene = Scene::new(test_scene());
+        assert_eq!(Resolution::NotFound, resolve(&mut scene, "eat it"));
+        let apple = resolve(&mut scene, "eat the dirty apple");
+        assert_eq!(apple, resolve(&mut scene, "eat it"));
+        match resolve(&mut scene, "eat all apples") {
+            Resolution::Many(found) => assert_eq!(
+                Resolution::Many(found),
+                resolve(&mut scene, "eat them")
+            ),
+            _ => panic!("should find every apple"),
+        }
+        assert_eq!(apple, resolve(&mut scene, "eat it"));
+    }
+
+    #[test]
+    fn test_pronouns_need_compatible_referent() {
+        let mut scene = Scene::new(test_scene());
+        resolve(&mut scene, "eat the clean apple");
+        assert!(scene.ask_question(&test_sentence("does the ghost exist")));
+        assert_eq!(
+            json!({
+                "noun": "ghost",
+                "adjectives": ["friendly"],
+                "gender": "female"
+            }),
+            unique(resolve(&mut scene, "eat her"), &scene)
+        );
+        assert_eq!(Resolution::NotFound, resolve(&mut scene, "eat him"));
+        assert_eq!(
+            json!({
+                "noun": "apple",
+                "adjectives": ["clean"],
+                "is": ["edible"],
+                "relation": "on"
+            }),
+            unique(resolve(&mut scene, "eat it"), &scene)
+        );
+        assert!(scene.ask_question(&test_sentence("is it edible")));
+        assert!(!scene.ask_question(&test_sentence("is her edible")));
+    }
+
+    #[test]
+    fn test_move_node_keeps_tree_intact() {
+        let mut scene = Scene::new(test_scene());
+        let table = scene.root.children[1].id;
+        let apple = scene.root.children[0].id;
+        let red_table = scene.root.children[1].children[0].id;
+        assert_eq!(
+            Err(MoveError::InsideItself),
+            scene.move_node(table, Some(red_table), None)
+        );
+        assert_eq!(
+            Err(MoveError::InsideItself),
+            scene.move_node(table, Some(table), None)
+        );
+        assert_eq!(Err(MoveError::Root), scene.move_node(0, Some(table), None));
+        assert_eq!(
+            Err(MoveError::ParentNotFound),
+            scene.move_node(apple, Some(100), None)
+        );
+        assert_eq!(test_scene(), scene.root);
+
+        assert_eq!(
+            Err(MoveError::UnknownRelation("near".to_string())),
+            scene.move_node(apple, Some(table), Some("near"))
+        );
+        let moved = scene.move_node(apple, Some(table), Some("under")).unwrap();
+        assert_eq!(
+            json!({
+                "noun": "apple",
+                "adjectives": ["red", "dirty"],
+                "is": ["edible"],
+                "relation": "under"
+            }),
+            moved.to_value()
+        );
+        assert_eq!(apple, scene.root.children[0].children[3].id);
+        assert_eq!(Some(vec![0, 3]), scene.index.path(apple));
+        assert_eq!(Some(vec![0, 0]), scene.index.path(red_table));
+        assert!(scene.hold(apple).is_ok());
+        assert!(scene.is_held(apple));
+        assert_eq!(7, scene.root.children[2].id);
+        assert_eq!(Some(apple), scene.get(apple).map(|x| x.id));
+        assert!(scene.remove(apple).is_some());
+        assert_eq!(None, scene.get(apple));
+        assert_eq!(None, scene.remove(100));
+        assert_eq!(Err(MoveError::NotFound), scene.hold(apple));
+    }
+
+    #[test]
+    fn test_hold_leaves_scene_unchanged_on_failure() {
+        let mut scene = Scene::new(test_scene());
+        assert_eq!(Err(MoveError::Root), scene.hold(0));
+        assert_eq!(Err(MoveError::NotFound), scene.hold(100));
+        assert_eq!(test_scene(), scene.root);
+        assert_eq!(None, scene.inventory());
+    }
+
+    #[test]
+    fn test_move_node_respects_capacity() {
+        let mut scene = Scene::from_value(&json!({
+            "children": [
+                {"noun": "box", "properties": {"capacity": 1}, "children": [
+                    {"noun": "knife"}
+                ]},
+                {"noun": "apple"}
+            ]
+        }))
+        .unwrap();
+        let full = scene.move_node(3, Some(1), None);
+        assert_eq!(Err(MoveError::Full { capacity: 1 }), full);
+        assert_eq!("the parent holds 1 already", full.unwrap_err().to_string());
+        // the knife is already in the box, so it only changes relation
+        assert!(scene.move_node(2, Some(1), Some("on")).is_ok());
+        assert!(scene.move_node(2, None, None).is_ok());
+        assert!(scene.move_node(3, Some(1), None).is_ok());
+        assert_eq!(
+            json!({"children": [
+                {"noun": "box", "properties": {"capacity": 1}, "children": [
+                    {"noun": "apple"}
+                ]},
+                {"noun": "knife"}
+            ]}),
+            scene.root.to_value()
+        );
+    }
+
+    #[test]
+    fn test_ask_with_spatial_prepositions() {
+        let mut scene = Scene::from_value(&json!({
+            "children": [
+                {"noun": "table", "children": [
+                    {"noun": "apple", "adjectives": ["red"], "relation": "on"},
+                    {"noun": "box", "relation": "under", "children": [
+                        {"noun": "apple", "adjectives": ["clean"]}
+                    ]}
+                ]},
+                {"noun": "ghost"}
+            ]
+        }))
+        .unwrap();
+        let mut ask = |text| scene.ask_question(&test_sentence(text));
+        assert!(ask("does the red apple on the table exist"));
+        assert!(!ask("does the red apple under the table exist"));
+        assert!(ask("does the box under the table exist"));
+        assert!(ask("does the clean apple in the box exist"));
+        assert!(ask("does the clean apple inside the box exist"));
+        assert!(ask("does the clean apple under the table exist"));
+        assert!(!ask("does the clean apple on the table exist"));
+        assert!(ask("does the ghost near the table exist"));
+        assert!(!ask("does the red apple near the table exist"));
+        assert!(!ask("does the red apple on the green table exist"));
+    }
+
+    #[test]
+    fn test_resolve_nested_modifiers() {
+        let mut scene = Scene::from_value(&json!({
+            "children": [
+                {"noun": "kitchen", "children": [
+                    {"noun": "table", "children": [
+                        {
+                            "noun": "apple",
+                            "adjectives": ["red"],
+                            "is": ["edible"],
+                            "relation": "on"
+                        }
+                    ]}
+                ]},
+                {"noun": "box", "children": [
+                    {"noun": "table", "children": [
+                        {
+                            "noun": "apple",
+                            "adjectives": ["clean"],
+                            "relation": "on"
+                        }
+                    ]}
+                ]}
+            ]
+        }))
+        .unwrap();
+        let red_apple = json!({
+            "noun": "apple",
+            "adjectives": ["red"],
+            "is": ["edible"],
+            "relation": "on"
+        });
+        match resolve(&mut scene, "eat the apple that is on the table") {
+            Resolution::Ambiguous(found) => assert_eq!(2, found.len()),
+            _ => panic!("both apples are on a table"),
+        }
+        assert_eq!(
+            red_apple,
+            unique(
+                resolve(
+                    &mut scene,
+                    "eat the apple that is on the table that is in the kitchen"
+                ),
+                &scene
+            )
+        );
+        let edible = resolve(&mut scene, "eat the apple that is edible");
+        assert_eq!(red_apple, unique(edible, &scene));
+        assert_eq!(
+            Resolution::NotFound,
+            resolve(
+                &mut scene,
+                "eat the apple that is in the kitchen that is clean"
+            )
+        );
+        assert!(scene.ask_question(&test_sentence(
+            "does the red apple on the table in the kitchen exist"
+        )));
+        assert!(!scene.ask_question(&test_sentence(
+            "does the clean apple on the table in the kitchen exist"
+        )));
+        assert!(scene.ask_question(&test_sentence(
+            "does the clean apple on the table in the box exist"
+        )));
+        // the table in the box, then the apple on it, not every apple
+        let sentence =
+            test_sentence("where is the apple on the table in the box");
+        match scene.answer(&sentence) {
+            Answer::Locations(found) => {
+                assert_eq!(1, found.len());
+                assert!(found[0].0.adjectives.contains(&"clean".to_string()));
+            }
+            _ => panic!("should locate the apple in the box"),
+        }
+    }
+
     #[test]
     fn test_remove_nothing() {
         let data = test_scene();
         let mut scene = Scene::new(data);
-        let filters: Vec<&fn(&Value) -> bool> = vec![&(super::edible as fn(&Value) -> bool)];
         let search_term =
-            NounClause::new("apple".to_string(), None, Some("red".to_string()));
-        scene.select(&search_term, &filters, Some(&(super::exist as fn(&Value) -> bool)));
+            NounClause::new("apple".to_string(), None, vec!["red".to_string()]);
+        let keep = |_: &mut Entity| true;
+        scene.select(&search_term, &is("edible"), Some(&keep));
 	assert_eq!(test_scene(), scene.root);
     }
 
@@ -222,13 +1260,13 @@ mod test {
     fn test_remove_edible() {
         let data = test_scene();
         let mut scene = Scene::new(data);
-        let filters: Vec<&fn(&Value) -> bool> = vec![&(super::edible as fn(&Value) -> bool)];
         let search_term =
-            NounClause::new("apple".to_string(), None, Some("red".to_string()));
-        let results = scene.select(&search_term, &filters, Some(&(super::remove as fn(&Value) -> bool)));
+            NounClause::new("apple".to_string(), None, vec!["red".to_string()]);
+        let remove = |_: &mut Entity| false;
+        let results = scene.select(&search_term, &is("edible"), Some(&remove));
         let sentence = test_sentence("does a red apple exist");
 	print!("{:?}\n\n", results);
-	print!("{}", scene.root);
+	print!("{:?}", scene.root);
         let result = scene.ask_question(&sentence);
         assert_eq!(false, result);
     }
@@ -255,20 +1293,105 @@ mod test {
         assert_eq!(false, scene.ask_question(&sentence_edible));
     }
 
+    #[test]
+    fn test_ask_registered_question_type() {
+        let mut scene = Scene::new(test_scene());
+        let sentence = test_sentence("is the clean apple heavy");
+        assert_eq!(false, scene.ask_question(&sentence));
+        let clean = |entity: &Entity| entity.adjectives == vec!["clean"];
+        scene.question_types.register("heavy", is("edible").and(clean));
+        assert_eq!(true, scene.ask_question(&sentence));
+        let sentence = test_sentence("is the blotchy apple heavy");
+        assert_eq!(false, scene.ask_question(&sentence));
+    }
+
+    #[test]
+    fn test_ask_about_components_and_adjectives() {
+        let mut scene = Scene::new(test_scene());
+        assert!(scene.ask_question(&test_sentence("is the red table red")));
+        assert!(!scene.ask_question(&test_sentence("is the clean apple red")));
+        let shiny = test_sentence("is the clean apple shiny");
+        assert!(!scene.ask_question(&shiny));
+        let apple = scene.root.children[1].children[2].id;
+        scene.update(apple, |node| node.components.push("shiny".to_string()));
+        assert!(scene.ask_question(&shiny));
+    }
+
+    #[test]
+    fn test_selected_ids_change_nodes_in_place() {
+        let mut scene = Scene::new(test_scene());
+        let clean = NounClause::new(
+            "apple".to_string(),
+            None,
+            vec!["clean".to_string()],
+        );
+        let green = NounClause::new(
+            "apple".to_string(),
+            None,
+            vec!["green".to_string()],
+        );
+        let found = scene.select(&clean, &exist, None);
+        assert_eq!(vec![5], found);
+        scene.update(found[0], |node| {
+            node.adjectives = vec!["green".to_string()]
+        });
+        assert!(scene.select(&clean, &exist, None).is_empty());
+        assert_eq!(found, scene.select(&green, &exist, None));
+        let table = scene.root.children[1].id;
+        scene.move_node(found[0], None, None).unwrap();
+        assert_eq!(found, scene.select(&green, &exist, None));
+        assert!(scene.related("on", table).iter().all(|x| *x != found[0]));
+    }
+
+    #[test]
+    fn test_scene_selects_with_every_adjective() {
+        let data = test_scene();
+        let mut scene = Scene::new(data);
+        let search_term = NounClause::new(
+            "apple".to_string(),
+            None,
+            vec!["blotchy".to_string(), "red".to_string()],
+        );
+        let result = scene.select(&search_term, &exist, None);
+        assert_eq!(
+            vec![
+                json!({
+                    "noun": "apple",
+                    "adjectives": ["blotchy", "red"],
+                    "is": ["edible"],
+                    "relation": "on"
+                }),
+            ],
+            result
+                .iter()
+                .map(|x| scene.get(*x).unwrap().to_value())
+                .collect::<Vec<Value>>()
+        );
+        let sentence = test_sentence("does a dirty blotchy apple exist");
+        assert_eq!(false, scene.ask_question(&sentence));
+    }
+
     #[test]
     fn test_scene_selects_multiple_items() {
         let data = test_scene();
         let mut scene = Scene::new(data);
-        let filters = Vec::new();
         let search_term =
-            NounClause::new("apple".to_string(), None, Some("red".to_string()));
-        let result = scene.select(&search_term, &filters, None);
+            NounClause::new("apple".to_string(), None, vec!["red".to_string()]);
+        let result = scene.select(&search_term, &exist, None);
         assert_eq!(
             vec![
                 json!({"noun": "apple", "adjectives": ["red", "dirty"], "is": ["edible"]}),
-                json!({"noun": "apple", "adjectives": ["blotchy", "red"], "is": ["edible"]}),
+                json!({
+                    "noun": "apple",
+                    "adjectives": ["blotchy", "red"],
+                    "is": ["edible"],
+                    "relation": "on"
+                }),
             ],
             result
+                .iter()
+                .map(|x| scene.get(*x).unwrap().to_value())
+                .collect::<Vec<Value>>()
         );
     }
 }
@@ -1,75 +1,272 @@
 use cfg::ast::AST;
 use cfg::ast_search::ASTSearch;
+use cfg::inflect;
 use cfg::lang::Lang;
-use serde_json::Value;
+use cfg::normalize::Normalizer;
+use cfg::normalize::Punctuation;
+use entity::Entity;
 use std::fmt;
+use std::ops::Range;
 
+/// How many nodes a noun clause refers to, from its article or number.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Quantity {
+    /// "the apple" refers to exactly one apple.
+    Definite,
+    /// "an apple" refers to any one apple.
+    Indefinite,
+    /// "2 apples" refers to at least that many apples.
+    Number(usize),
+    /// "all apples" refers to every apple.
+    All,
+    /// "apple" with no article, read like a definite article.
+    Unspecified,
+}
+
+impl Quantity {
+    fn from_ast<'ast>(search: &ASTSearch<'ast>) -> Quantity {
+        if let Some(number) = search.get_terminal("number") {
+            return match inflect::parse_number(&number) {
+                Some(number) => Quantity::Number(number),
+                None => Quantity::Unspecified,
+            };
+        }
+        if search.get_terminal("definiteArticle").is_some() {
+            Quantity::Definite
+        } else if search.get_terminal("indefiniteArticle").is_some() {
+            Quantity::Indefinite
+        } else if search.get_terminal("quantifier").is_some() {
+            Quantity::All
+        } else {
+            Quantity::Unspecified
+        }
+    }
+}
+
+/// A word standing for something mentioned earlier.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Pronoun {
+    /// "it" refers to a single thing.
+    It,
+    /// "them" refers to a group of things.
+    Them,
+    /// "him" refers to a single male character.
+    Him,
+    /// "her" refers to a single female character.
+    Her,
+}
+
+impl Pronoun {
+    /// Reads a pronoun lemma. Pronouns this does not know are read as
+    /// "it".
+    pub fn from_word(word: &str) -> Pronoun {
+        match word {
+            "them" | "they" => Pronoun::Them,
+            "him" | "he" => Pronoun::Him,
+            "her" | "she" => Pronoun::Her,
+            _ => Pronoun::It,
+        }
+    }
+}
+
+/// What an open question asks for.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Wh {
+    /// "what is on the table" asks for the nodes.
+    What,
+    /// "where is the apple" asks for the places holding a node.
+    Where,
+    /// "which apples are red" asks for the nodes, chosen among some.
+    Which,
+    /// "how many apples are there" asks for a count.
+    HowMany,
+}
+
+impl Wh {
+    fn from_ast<'ast>(search: ASTSearch<'ast>) -> Option<Wh> {
+        match search.get_terminal("wh")?.as_str() {
+            "what" => Some(Wh::What),
+            "where" => Some(Wh::Where),
+            "which" => Some(Wh::Which),
+            "how" if search.get_terminal("amount").is_some() => {
+                Some(Wh::HowMany)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Narrows down what a noun clause refers to.
+#[derive(Clone)]
+pub enum Modifier {
+    /// "on the table", from "the apple on the table" or "the apple that
+    /// is on the table".
+    Prep(PrepClause),
+    /// "edible", from "the apple that is edible".
+    Quality(String),
+}
+
+impl Modifier {
+    pub fn from_ast<'ast>(search: ASTSearch<'ast>) -> Option<Modifier> {
+        let prep = search.child_tree("PrepClause");
+        if prep.exists() {
+            return Some(Modifier::Prep(PrepClause::from_ast(prep)?));
+        }
+        quality_from_ast(search.child_tree("Type")).map(Modifier::Quality)
+    }
+}
+
+impl fmt::Display for Modifier {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Modifier::Prep(prep) => prep.fmt(fmt),
+            Modifier::Quality(quality) => write!(fmt, "Quality({})", quality),
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct NounClause {
+    /// The canonical noun, which is what the scene is matched against.
     pub noun: String,
+    /// The noun as the player typed it.
+    pub origin: String,
     count: Option<String>,
-    pub adjective: Option<String>,
+    /// How many nodes the clause refers to.
+    pub quantity: Quantity,
+    /// Every adjective of the clause. A node must have all of them.
+    pub adjectives: Vec<String>,
+    /// Set when the clause is a pronoun, which stands for whatever it
+    /// was last used to refer to rather than being matched itself.
+    pub pronoun: Option<Pronoun>,
+    /// Phrases narrowing down the nodes the clause refers to, each of
+    /// which may have modifiers of its own.
+    pub modifiers: Vec<Modifier>,
+    /// Set for "everything", or "all" on its own, which match any node.
+    pub universal: bool,
+    /// Set when the noun was typed in the plural, as "apples".
+    pub plural: bool,
 }
 
 impl NounClause {
     pub fn new(
         noun: String,
         count: Option<String>,
-        adjective: Option<String>,
+        adjectives: Vec<String>,
     ) -> NounClause {
         NounClause {
+            origin: noun.to_string(),
             noun: noun,
             count: count,
-            adjective: adjective,
+            quantity: Quantity::Unspecified,
+            adjectives: adjectives,
+            pronoun: None,
+            modifiers: Vec::new(),
+            universal: false,
+            plural: false,
         }
     }
 
+    /// A clause matching any node, such as "everything".
+    pub fn universal(word: String) -> NounClause {
+        let mut noun_clause = NounClause::new(word, None, Vec::new());
+        noun_clause.quantity = Quantity::All;
+        noun_clause.universal = true;
+        noun_clause
+    }
+
+    /// A clause made of just a pronoun, such as "it".
+    pub fn from_pronoun(word: String) -> NounClause {
+        let mut noun_clause = NounClause::new(word, None, Vec::new());
+        noun_clause.pronoun = Some(Pronoun::from_word(&noun_clause.noun));
+        noun_clause
+    }
+
     pub fn from_ast<'ast>(search: ASTSearch<'ast>) -> Option<NounClause> {
+        // "the apple on the table" and "the apple that is on the table"
+        // nest the clause they modify
+        let head = search.child_tree("NounClause");
+        if head.exists() {
+            let mut noun_clause = NounClause::from_ast(head)?;
+            let relative = search.child_tree("RelativeClause");
+            let modifier = if relative.exists() {
+                Modifier::from_ast(relative)?
+            } else {
+                Modifier::from_ast(search)?
+            };
+            noun_clause.modifiers.push(modifier);
+            return Some(noun_clause);
+        }
+        if let Some(pronoun) = search.get_terminal("pronoun") {
+            let mut noun_clause = NounClause::from_pronoun(pronoun);
+            noun_clause.origin = search.get_origin("pronoun")?;
+            return Some(noun_clause);
+        }
+        if let Some(universal) = search.get_terminal("universal") {
+            let mut noun_clause = NounClause::universal(universal);
+            noun_clause.origin = search.get_origin("universal")?;
+            return Some(noun_clause);
+        }
         let noun = search.get_terminal("noun")?;
         let count = search
             .get_terminal("number")
             .or(search.get_terminal("definiteArticle"))
-            .or(search.get_terminal("indefiniteArticle"));
-        let adjective = search.get_terminal("adjective");
-        Some(NounClause::new(noun, count, adjective))
-    }
-
-    pub fn matches(&self, value: &Value) -> bool {
-        // do we match the noun
-        match &value["noun"] {
-            Value::String(noun) => {
-                if &self.noun != noun {
-                    return false;
-                }
-            }
-            _ => return false,
-        }
-
-        // do we match the adjective
-        if self.adjective.is_none() {
-            return true;
-        }
-        if let Value::Array(adjectives) = &value["adjectives"] {
-            for adjective in adjectives {
-                // adjectives listed for an object should be non null
-                let found = match adjective {
-                    Value::String(adjective) => match &self.adjective {
-                        Some(desired) => {
-                            if adjective == desired {
-                                true
-                            } else {
-                                false
-                            }
-                        }
-                        _ => true,
-                    },
-                    _ => false,
-                };
-                if found {
-                    return true;
+            .or(search.get_terminal("indefiniteArticle"))
+            .or(search.get_terminal("quantifier"));
+        let adjectives = search.get_terminals("adjective");
+        let mut noun_clause = NounClause::new(noun, count, adjectives);
+        noun_clause.origin = search.get_origin("noun")?;
+        noun_clause.quantity = Quantity::from_ast(&search);
+        let origin = noun_clause.origin.to_lowercase();
+        // an alias such as "fruits" is plural by its own spelling
+        noun_clause.plural =
+            origin != noun_clause.noun && inflect::is_plural(&origin);
+        // "the apples" and "apples" refer to every apple
+        if noun_clause.plural {
+            match noun_clause.quantity {
+                Quantity::Definite | Quantity::Unspecified => {
+                    noun_clause.quantity = Quantity::All;
                 }
+                _ => (),
             }
         }
-        return false;
+        Some(noun_clause)
+    }
+
+    /// The article or number the clause was given, as "the" or "2".
+    pub fn count(&self) -> Option<&str> {
+        self.count.as_ref().map(|x| x.as_str())
+    }
+
+    /// Parses a noun clause on its own, such as the answer "the dirty
+    /// one" to a question. Returns None if the text is a sentence.
+    pub fn from_lang(lang: &Lang, text: &str) -> Option<NounClause> {
+        let ast = lang.parse_sentence(text).ok()?;
+        let search = ASTSearch::new(Some(&ast));
+        let sentence = ["Verb", "QuestionVerb", "Wh"]
+            .iter()
+            .any(|symbol| search.child_tree(symbol).exists());
+        if sentence {
+            return None;
+        }
+        NounClause::from_ast(search.child_tree("NounClause"))
+    }
+
+    pub fn matches(&self, entity: &Entity) -> bool {
+        self.matches_noun(entity) && self.matches_adjectives(entity)
+    }
+
+    pub fn matches_noun(&self, entity: &Entity) -> bool {
+        match &entity.noun {
+            Some(noun) => self.universal || &self.noun == noun,
+            None => false,
+        }
+    }
+
+    pub fn matches_adjectives(&self, entity: &Entity) -> bool {
+        self.adjectives
+            .iter()
+            .all(|desired| entity.adjectives.contains(desired))
     }
 }
 
@@ -80,12 +277,21 @@ impl fmt::Display for NounClause {
         fmt.write_str(", ")?;
         optional_write(fmt, &self.count)?;
         fmt.write_str(", ")?;
-        optional_write(fmt, &self.adjective)?;
+        if self.adjectives.is_empty() {
+            fmt.write_str("None")?;
+        } else {
+            fmt.write_str(&self.adjectives.join(" "))?;
+        }
+        for modifier in &self.modifiers {
+            fmt.write_str(", ")?;
+            modifier.fmt(fmt)?;
+        }
         fmt.write_str(")")?;
         Ok(())
     }
 }
 
+#[derive(Clone)]
 pub struct PrepClause {
     pub prep: String,
     pub noun_clause: NounClause,
@@ -101,8 +307,14 @@ impl PrepClause {
 
     pub fn from_ast<'ast>(search: ASTSearch<'ast>) -> Option<PrepClause> {
         let prep = search.get_terminal("prep")?;
-        let noun_clause =
+        let mut noun_clause =
             NounClause::from_ast(search.child_tree("NounClause"))?;
+        // "on the table in the kitchen" says where the table is
+        let nested = search.child_tree("PrepClause");
+        if nested.exists() {
+            let nested = PrepClause::from_ast(nested)?;
+            noun_clause.modifiers.push(Modifier::Prep(nested));
+        }
         Some(PrepClause::new(prep, noun_clause))
     }
 }
@@ -118,12 +330,22 @@ impl fmt::Display for PrepClause {
     }
 }
 
+#[derive(Clone)]
 pub struct Sentence {
+    /// The canonical verb.
     pub verb: String,
-    pub subject: NounClause,
+    /// The verb as the player typed it.
+    pub verb_origin: String,
+    /// Every noun clause the verb applies to, as "the apple" and "the
+    /// knife" in "take the apple and the knife". Never empty.
+    pub subjects: Vec<NounClause>,
+    /// Noun clauses left out by "except" or "but".
+    pub exceptions: Vec<NounClause>,
     pub prep: Option<PrepClause>,
     pub q_type: Option<String>,
     pub is_question: bool,
+    /// Set for open questions such as "where is the apple".
+    pub wh: Option<Wh>,
 }
 
 impl Sentence {
@@ -135,43 +357,127 @@ impl Sentence {
         is_question: bool,
     ) -> Sentence {
         Sentence {
-            subject: subject,
+            subjects: vec![subject],
+            exceptions: Vec::new(),
+            verb_origin: verb.to_string(),
             verb: verb,
             prep: prep,
             q_type: q_type,
             is_question: is_question,
+            wh: None,
         }
     }
 
+    /// The first subject, which is the only one a question has.
+    pub fn subject(&self) -> &NounClause {
+        &self.subjects[0]
+    }
+
     pub fn from_ast(ast: &AST) -> Option<Sentence> {
         let search = ASTSearch::new(Some(ast));
-        let subject = NounClause::from_ast(search.child_tree("NounClause"))?;
-        let mut verb = search.get_terminal("verb");
+        let mut subjects = search.child_trees("NounClause");
+        let mut exceptions = Vec::new();
+        let coordinated = search.child_tree("Subject");
+        if coordinated.exists() {
+            subjects = coordinated.child_trees("NounClause");
+            exceptions = coordinated
+                .child_tree("Exception")
+                .child_trees("NounClause");
+        }
+        let mut subjects = subjects
+            .into_iter()
+            .map(NounClause::from_ast)
+            .collect::<Option<Vec<NounClause>>>()?;
+        let exceptions = exceptions
+            .into_iter()
+            .map(NounClause::from_ast)
+            .collect::<Option<Vec<NounClause>>>()?;
+        let wh = search.child_tree("Wh");
+        let wh = if wh.exists() {
+            Some(Wh::from_ast(wh)?)
+        } else {
+            None
+        };
+        if subjects.is_empty() && wh.is_some() {
+            // "what is on the table" asks about anything on the table
+            let mut noun_clause =
+                NounClause::universal(search.get_terminal("wh")?);
+            noun_clause.origin = search.get_origin("wh")?;
+            subjects.push(noun_clause);
+        }
+        if subjects.is_empty() {
+            return None;
+        }
+        let subject = subjects.remove(0);
         let prep = PrepClause::from_ast(search.child_tree("PrepClause"));
+        let mut verb = search.child_tree("Verb");
+        let mut verb_symbol = "verb";
         let mut is_question = false;
         let mut q_type = None;
-        if verb.is_none() {
+        if !verb.exists() {
+            // a question's verb is part of the sentence itself, unlike
+            // the one in "the apple that is red"
+            verb = search.child_tree("QuestionVerb");
+            verb_symbol = "qVerb";
             is_question = true;
-            verb = search.get_terminal("qVerb");
-            q_type = search.get_terminal("type");
+            q_type = quality_from_ast(search.child_tree("Type"));
         }
-        let verb = verb?;
-        Some(Sentence::new(subject, verb, prep, q_type, is_question))
+        let mut sentence = Sentence::new(
+            subject,
+            verb.get_terminal(verb_symbol)?,
+            prep,
+            q_type,
+            is_question,
+        );
+        sentence.verb_origin = verb.get_origin(verb_symbol)?;
+        sentence.subjects.extend(subjects);
+        sentence.exceptions = exceptions;
+        sentence.wh = wh;
+        Some(sentence)
     }
 
     pub fn from_lang(lang: &Lang, sentence: &str) -> Result<Sentence, String> {
-        let ast = lang.parse_sentence(sentence)?;
+        let ast = lang
+            .parse_sentence(sentence)
+            .map_err(|error| error.to_string())?;
         match Sentence::from_ast(&ast) {
             Some(sentence) => Ok(sentence),
             None => Err("bad sentence".to_string()),
         }
     }
+
+    /// Every reading of an ambiguous sentence, flattest first, for the
+    /// scene to choose from. Readings that are not sentences are left
+    /// out.
+    pub fn readings(
+        lang: &Lang,
+        sentence: &str,
+    ) -> Result<Vec<Sentence>, String> {
+        let asts = lang
+            .parse_sentence_all(sentence)
+            .map_err(|error| error.to_string())?;
+        let readings: Vec<Sentence> =
+            asts.iter().filter_map(Sentence::from_ast).collect();
+        if readings.is_empty() {
+            return Err("bad sentence".to_string());
+        }
+        Ok(readings)
+    }
 }
 
 impl fmt::Display for Sentence {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         fmt.write_str("Sentence(")?;
-        self.subject.fmt(fmt)?;
+        for (i, subject) in self.subjects.iter().enumerate() {
+            if i > 0 {
+                fmt.write_str(" and ")?;
+            }
+            subject.fmt(fmt)?;
+        }
+        for (i, exception) in self.exceptions.iter().enumerate() {
+            fmt.write_str(if i > 0 { " and " } else { " except " })?;
+            exception.fmt(fmt)?;
+        }
         fmt.write_str(", ")?;
         fmt.write_str(&self.verb)?;
         fmt.write_str(", ")?;
@@ -185,11 +491,46 @@ impl fmt::Display for Sentence {
         optional_write(fmt, &self.q_type)?;
         fmt.write_str(", ")?;
         self.is_question.fmt(fmt)?;
+        if let Some(wh) = &self.wh {
+            write!(fmt, ", {:?}", wh)?;
+        }
         fmt.write_str(")")?;
         Ok(())
     }
 }
 
+/// Splits what the player typed into the commands it is made of, as
+/// "take the apple then eat it" or "take the apple. eat it". Only a full
+/// stop after a word ends a command, so "the 1.5 m box" is kept whole.
+pub fn split_commands(text: &str) -> Vec<String> {
+    let mut normalizer = Normalizer::new();
+    normalizer.punctuation = Punctuation::Tokenize;
+    let mut commands = Vec::new();
+    let mut command: Option<Range<usize>> = None;
+    for token in normalizer.tokenize(text) {
+        if token.word == "then" || token.word == "." {
+            if let Some(span) = command.take() {
+                commands.push(text[span].to_string());
+            }
+        } else {
+            let start = command.map_or(token.span.start, |x| x.start);
+            command = Some(start..token.span.end);
+        }
+    }
+    if let Some(span) = command {
+        commands.push(text[span].to_string());
+    }
+    commands
+}
+
+/// The type of a question or relative clause, which may be an adjective
+/// as in "is the apple red".
+fn quality_from_ast<'ast>(search: ASTSearch<'ast>) -> Option<String> {
+    search
+        .get_terminal("type")
+        .or(search.get_terminal("adjective"))
+}
+
 fn optional_write(
     fmt: &mut fmt::Formatter,
     o_str: &Option<String>,
@@ -204,15 +545,12 @@ fn optional_write(
 
 #[cfg(test)]
 mod test {
+    use super::split_commands;
+    use super::Pronoun;
+    use super::Quantity;
     use super::Sentence;
-    use cfg::lang::Lang;
-
-    fn make_lang() -> Lang {
-        Lang::from_file(
-            "rust/test-data/test-lang-rules.txt",
-            "rust/test-data/test-lang-words.txt",
-        )
-    }
+    use super::Wh;
+    use test_util::make_lang;
 
     #[test]
     fn test_sentence_from_ast() {
@@ -223,6 +561,168 @@ mod test {
         assert_eq!("Sentence(NounClause(apple, the, green), eat, PrepClause(on, NounClause(table, a, None)), None, false)", format!("{}", sentence));
     }
 
+    #[test]
+    fn test_sentence_keeps_typed_words_and_lemmas() {
+        let lang = make_lang();
+        let sentence =
+            Sentence::from_lang(&lang, "Consume the green fruit").unwrap();
+        assert_eq!("eat", sentence.verb);
+        assert_eq!("Consume", sentence.verb_origin);
+        assert_eq!("apple", sentence.subject().noun);
+        assert_eq!("fruit", sentence.subject().origin);
+    }
+
+    #[test]
+    fn test_sentence_keeps_every_adjective() {
+        let lang = make_lang();
+        let sentence =
+            Sentence::from_lang(&lang, "eat the blotchy red apple").unwrap();
+        assert_eq!(
+            "Sentence(NounClause(apple, the, blotchy red), eat, None, None, \
+false)",
+            format!("{}", sentence)
+        );
+    }
+
+    #[test]
+    fn test_sentence_with_plurals_and_numbers() {
+        let lang = make_lang();
+        let sentence = Sentence::from_lang(&lang, "eat the Apples").unwrap();
+        assert_eq!("apple", sentence.subject().noun);
+        assert!(sentence.subject().plural);
+        assert_eq!(Quantity::All, sentence.subject().quantity);
+        let sentence = Sentence::from_lang(&lang, "eat 12 apples").unwrap();
+        assert_eq!(Quantity::Number(12), sentence.subject().quantity);
+        let sentence = Sentence::from_lang(&lang, "eat five boxes").unwrap();
+        assert_eq!("box", sentence.subject().noun);
+        assert_eq!(Quantity::Number(5), sentence.subject().quantity);
+        let sentence = Sentence::from_lang(&lang, "eat the apple").unwrap();
+        assert!(!sentence.subject().plural);
+        assert_eq!(Quantity::Definite, sentence.subject().quantity);
+    }
+
+    #[test]
+    fn test_sentence_with_pronoun() {
+        let lang = make_lang();
+        let sentence = Sentence::from_lang(&lang, "eat Them").unwrap();
+        assert_eq!(Some(Pronoun::Them), sentence.subject().pronoun);
+        assert_eq!("them", sentence.subject().noun);
+        assert_eq!("Them", sentence.subject().origin);
+    }
+
+    #[test]
+    fn test_sentence_with_nested_modifiers() {
+        let lang = make_lang();
+        let sentence = Sentence::from_lang(
+            &lang,
+            "take the apple on the table in the kitchen",
+        )
+        .unwrap();
+        assert_eq!(
+            "Sentence(NounClause(apple, the, None), take, PrepClause(on, \
+NounClause(table, the, None, PrepClause(in, NounClause(kitchen, the, None)))), \
+None, false)",
+            format!("{}", sentence)
+        );
+        let sentence = Sentence::from_lang(
+            &lang,
+            "where is the apple on the table in the kitchen",
+        )
+        .unwrap();
+        assert_eq!(
+            "NounClause(apple, the, None, PrepClause(on, NounClause(table, \
+the, None, PrepClause(in, NounClause(kitchen, the, None)))))",
+            format!("{}", sentence.subject())
+        );
+        let sentence =
+            Sentence::from_lang(&lang, "eat the apple that is on the table")
+                .unwrap();
+        assert_eq!(
+            "NounClause(apple, the, None, PrepClause(on, NounClause(table, \
+the, None)))",
+            format!("{}", sentence.subject())
+        );
+        let sentence = Sentence::from_lang(
+            &lang,
+            "is the apple that is edible that is in the kitchen red",
+        )
+        .unwrap();
+        assert_eq!(
+            "Sentence(NounClause(apple, the, None, Quality(edible), \
+PrepClause(in, NounClause(kitchen, the, None))), is, None, red, true)",
+            format!("{}", sentence)
+        );
+    }
+
+    #[test]
+    fn test_sentence_with_several_subjects() {
+        let lang = make_lang();
+        let sentence =
+            Sentence::from_lang(&lang, "take the apple and the knife").unwrap();
+        assert_eq!(
+            "Sentence(NounClause(apple, the, None) and NounClause(knife, the, \
+None), take, None, None, false)",
+            format!("{}", sentence)
+        );
+        let sentence = Sentence::from_lang(
+            &lang,
+            "take everything except the red apple and the knife",
+        )
+        .unwrap();
+        assert!(sentence.subject().universal);
+        assert_eq!(
+            "Sentence(NounClause(everything, None, None) except \
+NounClause(apple, the, red) and NounClause(knife, the, None), take, None, \
+None, false)",
+            format!("{}", sentence)
+        );
+        let sentence =
+            Sentence::from_lang(&lang, "take all but the knife").unwrap();
+        assert!(sentence.subject().universal);
+        assert_eq!(1, sentence.exceptions.len());
+    }
+
+    #[test]
+    fn test_wh_questions() {
+        let lang = make_lang();
+        let sentence =
+            Sentence::from_lang(&lang, "what is on the table").unwrap();
+        assert!(sentence.subject().universal);
+        assert_eq!(
+            "Sentence(NounClause(what, None, None), is, PrepClause(on, \
+NounClause(table, the, None)), None, true, What)",
+            format!("{}", sentence)
+        );
+        let sentence =
+            Sentence::from_lang(&lang, "where is the apple").unwrap();
+        assert_eq!(Some(Wh::Where), sentence.wh);
+        assert_eq!("apple", sentence.subject().noun);
+        let sentence =
+            Sentence::from_lang(&lang, "which apples are red").unwrap();
+        assert_eq!(Some(Wh::Which), sentence.wh);
+        assert_eq!(Some("red".to_string()), sentence.q_type);
+        let sentence =
+            Sentence::from_lang(&lang, "how many apples are there").unwrap();
+        assert_eq!(Some(Wh::HowMany), sentence.wh);
+        assert_eq!("apples", sentence.subject().origin);
+        let sentence =
+            Sentence::from_lang(&lang, "is the apple edible").unwrap();
+        assert_eq!(None, sentence.wh);
+    }
+
+    #[test]
+    fn test_split_commands() {
+        assert_eq!(
+            vec!["take the apple", "eat it", "drop the knife"],
+            split_commands("take the apple then eat it. drop the knife.")
+        );
+        assert_eq!(vec!["eat it"], split_commands("Then eat it"));
+        assert_eq!(
+            vec!["examine the 1.5 m box", "Open it"],
+            split_commands("examine the 1.5 m box.  Open it")
+        );
+    }
+
     #[test]
     fn test_question_sentence_from_ast() {
         let lang = make_lang();
@@ -231,4 +731,15 @@ mod test {
         let sentence = Sentence::from_ast(&ast).unwrap();
         assert_eq!("Sentence(NounClause(apple, the, green), does, PrepClause(on, NounClause(table, a, None)), exist, true)", format!("{}", sentence));
     }
+
+    #[test]
+    fn test_fragments_are_not_questions() {
+        let lang = make_lang();
+        let ast = lang.parse_sentence("the apple that is red").unwrap();
+        assert!(Sentence::from_ast(&ast).is_none());
+        let ast = lang.parse_sentence("the apple on the table").unwrap();
+        assert!(Sentence::from_ast(&ast).is_none());
+        let ast = lang.parse_sentence("is the apple red").unwrap();
+        assert!(Sentence::from_ast(&ast).unwrap().is_question);
+    }
 }
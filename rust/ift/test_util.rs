@@ -0,0 +1,30 @@
+use cfg::lang::Lang;
+use entity::Entity;
+use sentence::Sentence;
+use serde_json::Value;
+use std::fs;
+
+/// The scene the tests play in, as its root node.
+pub fn test_scene() -> Entity {
+    let scene = fs::read_to_string("rust/test-data/test-scene.json").unwrap();
+    Entity::from_json(&scene).unwrap()
+}
+
+pub fn make_lang() -> Lang {
+    let rules = fs::read_to_string("rust/test-data/test-lang-rules.txt");
+    let words = fs::read_to_string("rust/test-data/test-lang-words.txt");
+    let mut lang = Lang::new();
+    lang.init_rules(&rules.unwrap());
+    lang.init_words(&words.unwrap());
+    lang.set_inflections("noun", "number");
+    lang
+}
+
+pub fn test_sentence(sentence: &str) -> Sentence {
+    Sentence::from_lang(&make_lang(), sentence).unwrap()
+}
+
+/// A node on its own, outside of any scene.
+pub fn entity(value: Value) -> Entity {
+    Entity::from_value(&value).unwrap()
+}
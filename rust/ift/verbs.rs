@@ -0,0 +1,638 @@
+use conversation::clarification;
+use describe::capitalize;
+use describe::definite;
+use describe::describe;
+use describe::label;
+use predicate::is;
+use predicate::Predicate;
+use scene::MoveError;
+use scene::Resolution;
+use scene::Scene;
+use sentence::NounClause;
+use sentence::Sentence;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// What a handler is asked to do with a single node. Nodes are given by
+/// id and read from the scene with `Scene::get`, as an earlier action
+/// may have changed or removed them.
+pub struct Action<'a> {
+    /// The sentence the player entered.
+    pub sentence: &'a Sentence,
+    /// The id of the node the verb acts on.
+    pub subject: usize,
+    /// The id of the node named by the prepositional clause of a verb
+    /// that takes an object, as the table in "put the apple on the
+    /// table".
+    pub object: Option<usize>,
+    /// Whether the verb acts on several nodes, so each line of narration
+    /// is already labelled with the node it is about.
+    pub listed: bool,
+}
+
+impl<'a> Action<'a> {
+    /// Names the subject for narration: "it" when the line is labelled
+    /// with the subject already or it is no longer in the scene,
+    /// otherwise as `definite` does.
+    pub fn name(&self, scene: &Scene) -> String {
+        match scene.get(self.subject) {
+            Some(subject) if !self.listed => definite(subject),
+            _ => "it".to_string(),
+        }
+    }
+}
+
+/// Carries out a verb by changing the scene, returning what the player
+/// is told.
+pub type Handler = Box<dyn Fn(&mut Scene, &Action) -> String>;
+
+/// Maps verbs to the handlers that carry them out.
+pub struct Verbs {
+    handlers: HashMap<String, Handler>,
+    /// The verbs whose prepositional clause names an object.
+    objects: HashSet<String>,
+}
+
+impl Verbs {
+    /// A registry knowing eat, take, drop, put, open and examine.
+    pub fn new() -> Verbs {
+        let mut verbs = Verbs::empty();
+        verbs.register("eat", eat);
+        verbs.register("take", take);
+        verbs.register("drop", drop);
+        verbs.register_with_object("put", put);
+        verbs.register("open", open);
+        verbs.register("examine", examine);
+        verbs
+    }
+
+    /// A registry without any verbs.
+    pub fn empty() -> Verbs {
+        Verbs {
+            handlers: HashMap::new(),
+            objects: HashSet::new(),
+        }
+    }
+
+    /// Adds a verb, replacing any handler it already had.
+    ///
+    /// # Arguements
+    ///
+    /// * `verb` - The canonical verb, as it appears in the words file.
+    /// * `handler` - Carries out the verb for one node at a time.
+    pub fn register<F>(&mut self, verb: &str, handler: F)
+    where
+        F: Fn(&mut Scene, &Action) -> String + 'static,
+    {
+        self.objects.remove(verb);
+        self.handlers.insert(verb.to_string(), Box::new(handler));
+    }
+
+    /// Adds a verb whose prepositional clause names the object it acts
+    /// with, as "on the table" in "put the apple on the table".
+    ///
+    /// # Arguements
+    ///
+    /// * `verb` - The canonical verb, as it appears in the words file.
+    /// * `handler` - Carries out the verb for one node at a time.
+    pub fn register_with_object<F>(&mut self, verb: &str, handler: F)
+    where
+        F: Fn(&mut Scene, &Action) -> String + 'static,
+    {
+        self.register(verb, handler);
+        self.objects.insert(verb.to_string());
+    }
+
+    /// Whether a handler is registered for the verb.
+    pub fn knows(&self, verb: &str) -> bool {
+        self.handlers.contains_key(verb)
+    }
+
+    /// Whether the verb takes an object.
+    pub fn takes_object(&self, verb: &str) -> bool {
+        self.objects.contains(verb)
+    }
+
+    /// Resolves the subjects of an imperative sentence, and its object if
+    /// the verb takes one. Otherwise the prepositional clause tells where
+    /// the subjects are, as in "eat the apple on the table".
+    ///
+    /// # Arguements
+    ///
+    /// * `scene` - The scene the sentence is looked up in.
+    /// * `sentence` - The sentence the player entered.
+    pub fn resolve(
+        &self,
+        scene: &mut Scene,
+        sentence: &Sentence,
+    ) -> (Vec<Resolution>, Option<Resolution>) {
+        if !self.takes_object(&sentence.verb) {
+            let location = sentence.prep.as_ref();
+            return (scene.resolve_subjects(sentence, location), None);
+        }
+        let resolutions = scene.resolve_subjects(sentence, None);
+        let object = sentence
+            .prep
+            .as_ref()
+            .map(|prep| scene.resolve(&prep.noun_clause));
+        (resolutions, object)
+    }
+
+    /// Picks the reading of a sentence that fits the scene best, the one
+    /// whose subjects and object are found most definitely.
+    /// Of readings that fit as well, the first is kept. Returns None if
+    /// there are no readings.
+    ///
+    /// # Arguements
+    ///
+    /// * `scene` - The scene the readings are looked up in.
+    /// * `readings` - The readings, as from `Sentence::readings`.
+    pub fn choose(
+        &self,
+        scene: &mut Scene,
+        readings: Vec<Sentence>,
+    ) -> Option<Sentence> {
+        readings
+            .into_iter()
+            .min_by_key(|reading| self.misfit(scene, reading))
+    }
+
+    /// How badly a reading fits the scene, adding up how far each of its
+    /// noun clauses is from being found.
+    fn misfit(&self, scene: &mut Scene, sentence: &Sentence) -> usize {
+        let takes_object = self.takes_object(&sentence.verb);
+        let location = if takes_object {
+            None
+        } else {
+            sentence.prep.as_ref()
+        };
+        let mut total: usize = sentence
+            .subjects
+            .iter()
+            .map(|subject| misfit(&scene.find(subject, location)))
+            .sum();
+        if takes_object {
+            total += match &sentence.prep {
+                Some(prep) => misfit(&scene.find(&prep.noun_clause, None)),
+                None => misfit(&Resolution::NotFound),
+            };
+        }
+        total
+    }
+
+    /// Carries out an imperative sentence whose subjects have been
+    /// resolved, once for every node they refer to and in order. When
+    /// there are several, each line is labelled with what it is about, as
+    /// in "red apple: You take it.".
+    ///
+    /// # Arguements
+    ///
+    /// * `scene` - The scene the sentence changes.
+    /// * `sentence` - The sentence the player entered.
+    /// * `resolutions` - What each subject of the sentence refers to.
+    /// * `object` - What the object refers to, if the verb takes one.
+    pub fn execute(
+        &self,
+        scene: &mut Scene,
+        sentence: &Sentence,
+        resolutions: Vec<Resolution>,
+        object: Option<Resolution>,
+    ) -> String {
+        let handler = match self.handlers.get(&sentence.verb) {
+            Some(handler) => handler,
+            None => {
+                return format!(
+                    "You don't know how to {}.",
+                    sentence.verb_origin
+                )
+            }
+        };
+        let found: Vec<Result<Vec<usize>, String>> = sentence
+            .subjects
+            .iter()
+            .zip(resolutions)
+            .map(|(subject, resolution)| match resolution {
+                Resolution::Unique(node) => Ok(vec![node]),
+                Resolution::Many(nodes) => Ok(nodes),
+                Resolution::Ambiguous(candidates) => {
+                    Err(clarification(subject, &scene.entities(&candidates)))
+                }
+                Resolution::Insufficient { wanted, found } => Err(format!(
+                    "You only see {} of the {} {} you wanted.",
+                    found.len(),
+                    wanted,
+                    subject.origin
+                )),
+                Resolution::NotFound => Err(not_found(subject)),
+            })
+            .collect();
+        let subjects: Vec<usize> = found
+            .iter()
+            .flat_map(|x| x.clone().unwrap_or_default())
+            .collect();
+        let listed = found.len() > 1 || subjects.len() > 1;
+        if !listed {
+            if let Some(Err(message)) = found.first() {
+                return message.to_string();
+            }
+        }
+        let object = match (&sentence.prep, object) {
+            (Some(prep), Some(resolution)) => match resolution {
+                Resolution::Unique(node) => Some(node),
+                Resolution::Ambiguous(candidates) => {
+                    let candidates = scene.entities(&candidates);
+                    return clarification(&prep.noun_clause, &candidates);
+                }
+                _ => return not_found(&prep.noun_clause),
+            },
+            _ => None,
+        };
+        // the subjects stay what later pronouns refer to
+        scene.mention(&if subjects.len() == 1 {
+            Resolution::Unique(subjects[0])
+        } else {
+            Resolution::Many(subjects.clone())
+        });
+
+        let mut narration = Vec::new();
+        for (subject, nodes) in sentence.subjects.iter().zip(found) {
+            let nodes = match nodes {
+                Ok(nodes) => nodes,
+                Err(message) => {
+                    narration.push(format!("{}: {}", subject.origin, message));
+                    continue;
+                }
+            };
+            for node in nodes {
+                // labelled before the handler, which may remove the node
+                let labelled = scene.get(node).map(label);
+                let action = Action {
+                    sentence: sentence,
+                    subject: node,
+                    object: object,
+                    listed: listed,
+                };
+                let text = handler(scene, &action);
+                narration.push(match labelled {
+                    Some(labelled) if listed => {
+                        format!("{}: {}", labelled, text)
+                    }
+                    _ => text,
+                });
+            }
+        }
+        narration.join("\n")
+    }
+}
+
+impl Default for Verbs {
+    fn default() -> Verbs {
+        Verbs::new()
+    }
+}
+
+/// How far a resolution is from settling on what was meant.
+fn misfit(resolution: &Resolution) -> usize {
+    match resolution {
+        Resolution::Unique(_) | Resolution::Many(_) => 0,
+        Resolution::Ambiguous(_) => 1,
+        Resolution::Insufficient { .. } => 2,
+        Resolution::NotFound => 3,
+    }
+}
+
+fn not_found(noun_clause: &NounClause) -> String {
+    if noun_clause.pronoun.is_some() {
+        format!("You don't know what \"{}\" means here.", noun_clause.origin)
+    } else {
+        format!("You don't see any {} here.", noun_clause.origin)
+    }
+}
+
+fn eat(scene: &mut Scene, action: &Action) -> String {
+    let name = action.name(scene);
+    let edible = match scene.get(action.subject) {
+        Some(subject) => is("edible").test(subject),
+        None => return format!("{} is gone.", capitalize(&name)),
+    };
+    if !edible {
+        return format!("You can't eat {}.", name);
+    }
+    match scene.remove(action.subject) {
+        Some(_) => format!("You eat {}.", name),
+        None => format!("{} is gone.", capitalize(&name)),
+    }
+}
+
+fn take(scene: &mut Scene, action: &Action) -> String {
+    let name = action.name(scene);
+    if scene.is_held(action.subject) {
+        return format!("You already have {}.", name);
+    }
+    match scene.hold(action.subject) {
+        Ok(_) => format!("You take {}.", name),
+        Err(MoveError::NotFound) => format!("{} is gone.", capitalize(&name)),
+        Err(MoveError::Full { .. }) => "You can't carry any more.".to_string(),
+        Err(_) => format!("You can't take {}.", name),
+    }
+}
+
+fn drop(scene: &mut Scene, action: &Action) -> String {
+    let name = action.name(scene);
+    if !scene.is_held(action.subject) {
+        return format!("You aren't holding {}.", name);
+    }
+    match scene.move_node(action.subject, None, None) {
+        Ok(_) => format!("You drop {}.", name),
+        Err(_) => format!("You can't drop {}.", name),
+    }
+}
+
+fn put(scene: &mut Scene, action: &Action) -> String {
+    let name = action.name(scene);
+    let object = action.object.and_then(|object| scene.get(object));
+    let (prep, id, object) = match (&action.sentence.prep, object) {
+        (Some(prep), Some(node)) => (&prep.prep, node.id, definite(node)),
+        _ => return format!("Where do you want to put {}?", name),
+    };
+    let narration = format!("{} {} {}", name, prep, object);
+    match scene.move_node(action.subject, Some(id), Some(prep)) {
+        Ok(_) => format!("You put {}.", narration),
+        Err(MoveError::Full { .. }) => {
+            format!("There is no room {} {}.", prep, object)
+        }
+        Err(MoveError::InsideItself) if id == action.subject => {
+            format!("You can't put {} {} itself.", name, prep)
+        }
+        Err(MoveError::InsideItself) => {
+            format!("You can't put {}, since {} holds it.", narration, name)
+        }
+        Err(_) => format!("You can't put {}.", narration),
+    }
+}
+
+fn open(scene: &mut Scene, action: &Action) -> String {
+    let name = action.name(scene);
+    let (openable, open) = match scene.get(action.subject) {
+        Some(subject) => {
+            (is("openable").test(subject), is("open").test(subject))
+        }
+        None => return format!("{} is gone.", capitalize(&name)),
+    };
+    if !openable {
+        return format!("You can't open {}.", name);
+    }
+    if open {
+        return format!("{} is already open.", capitalize(&name));
+    }
+    scene.update(action.subject, |node| {
+        node.components.push("open".to_string())
+    });
+    format!("You open {}.", name)
+}
+
+fn examine(scene: &mut Scene, action: &Action) -> String {
+    match scene.get(action.subject) {
+        Some(subject) => describe(subject),
+        None => format!("{} is gone.", capitalize(&action.name(scene))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Verbs;
+    use describe::definite;
+    use scene::Scene;
+    use sentence::Sentence;
+    use test_util::make_lang;
+    use test_util::test_scene;
+
+    fn run(verbs: &Verbs, scene: &mut Scene, text: &str) -> String {
+        let readings = Sentence::readings(&make_lang(), text).unwrap();
+        let sentence = verbs.choose(scene, readings).unwrap();
+        let (resolutions, object) = verbs.resolve(scene, &sentence);
+        verbs.execute(scene, &sentence, resolutions, object)
+    }
+
+    #[test]
+    fn test_eat_removes_edible_nodes() {
+        let verbs = Verbs::new();
+        let mut scene = Scene::new(test_scene());
+        assert_eq!(
+            "You eat the blotchy red apple.",
+            run(&verbs, &mut scene, "eat the blotchy apple")
+        );
+        assert_eq!(
+            "You don't see any apple here.",
+            run(&verbs, &mut scene, "eat the blotchy apple")
+        );
+        assert_eq!(
+            "You can't eat the red table.",
+            run(&verbs, &mut scene, "eat the red table")
+        );
+        assert_eq!(
+            "dirty red apple: You eat it.\nclean apple: You eat it.",
+            run(&verbs, &mut scene, "eat all apples")
+        );
+    }
+
+    #[test]
+    fn test_prep_locates_subjects() {
+        let verbs = Verbs::new();
+        let mut scene = Scene::new(test_scene());
+        assert_eq!(
+            "You don't see any apple here.",
+            run(&verbs, &mut scene, "eat the apple under the red table")
+        );
+        assert_eq!(
+            "Which do you mean, the blotchy red apple or the clean apple?",
+            run(&verbs, &mut scene, "eat the apple on a table")
+        );
+        assert_eq!(
+            "You eat the blotchy red apple.",
+            run(&verbs, &mut scene, "eat the red apple on the table")
+        );
+        assert_eq!(
+            "You take the clean apple.",
+            run(&verbs, &mut scene, "take an apple on a table")
+        );
+    }
+
+    #[test]
+    fn test_take_drop_and_put() {
+        let verbs = Verbs::new();
+        let mut scene = Scene::new(test_scene());
+        assert_eq!(
+            "You take the clean apple.",
+            run(&verbs, &mut scene, "take the clean apple")
+        );
+        assert_eq!(
+            "You already have the clean apple.",
+            run(&verbs, &mut scene, "take it")
+        );
+        assert_eq!(
+            "You drop the clean apple.",
+            run(&verbs, &mut scene, "drop it")
+        );
+        assert_eq!(
+            "You aren't holding the clean apple.",
+            run(&verbs, &mut scene, "drop it")
+        );
+        assert_eq!(
+            "You put the clean apple on the red table.",
+            run(&verbs, &mut scene, "put the clean apple on the red table")
+        );
+        assert_eq!(
+            "You see the red table. On it is a clean apple.",
+            run(&verbs, &mut scene, "examine the red table")
+        );
+        assert_eq!(
+            "Where do you want to put the clean apple?",
+            run(&verbs, &mut scene, "put the clean apple")
+        );
+    }
+
+    #[test]
+    fn test_actions_read_the_scene_as_it_is() {
+        let verbs = Verbs::new();
+        let mut scene = Scene::new(test_scene());
+        let sentence =
+            Sentence::from_lang(&make_lang(), "eat the clean apple").unwrap();
+        let (resolutions, object) = verbs.resolve(&mut scene, &sentence);
+        // the apple changes between being found and being eaten
+        let apple = resolutions[0].ids()[0];
+        scene.update(apple, |node| node.adjectives.push("green".to_string()));
+        assert_eq!(
+            "You eat the clean green apple.",
+            verbs.execute(&mut scene, &sentence, resolutions.clone(), None)
+        );
+        assert_eq!(
+            "It is gone.",
+            verbs.execute(&mut scene, &sentence, resolutions, object)
+        );
+    }
+
+    #[test]
+    fn test_reading_is_chosen_by_scene() {
+        let verbs = Verbs::new();
+        let mut scene = Scene::from_value(&json!({
+            "children": [
+                {"noun": "kitchen", "children": [{"noun": "apple"}]},
+                {"noun": "table", "children": [
+                    {"noun": "apple", "adjectives": ["red"], "relation": "on"}
+                ]},
+                {"noun": "box"}
+            ]
+        }))
+        .unwrap();
+        // not "on the table in the box", as there is no such table
+        assert_eq!(
+            "You put the red apple in the box.",
+            run(&verbs, &mut scene, "put the apple on the table in the box")
+        );
+    }
+
+    #[test]
+    fn test_put_checks_capacity_and_containment() {
+        let verbs = Verbs::new();
+        let mut scene = Scene::from_value(&json!({
+            "children": [
+                {"noun": "table", "children": [
+                    {"noun": "apple", "relation": "on"}
+                ]},
+                {"noun": "box", "properties": {"capacity": 1}, "children": [
+                    {"noun": "knife"}
+                ]}
+            ]
+        }))
+        .unwrap();
+        assert_eq!(
+            "You can't put the table in the apple, since the table holds it.",
+            run(&verbs, &mut scene, "put the table in the apple")
+        );
+        assert_eq!(
+            "You can't put the table on itself.",
+            run(&verbs, &mut scene, "put the table on the table")
+        );
+        assert_eq!(
+            "There is no room in the box.",
+            run(&verbs, &mut scene, "put the apple in the box")
+        );
+        assert_eq!(
+            "You put the knife on the table.",
+            run(&verbs, &mut scene, "put the knife on the table")
+        );
+        assert_eq!(
+            "You put the apple in the box.",
+            run(&verbs, &mut scene, "put the apple in the box")
+        );
+    }
+
+    #[test]
+    fn test_several_subjects_are_narrated_in_order() {
+        let verbs = Verbs::new();
+        let mut scene = Scene::from_value(&json!({
+            "children": [
+                {"noun": "apple", "adjectives": ["red"]},
+                {"noun": "apple", "adjectives": ["clean"]},
+                {"noun": "knife"}
+            ]
+        }))
+        .unwrap();
+        assert_eq!(
+            "red apple: You take it.\nbox: You don't see any box here.",
+            run(&verbs, &mut scene, "take the red apple and the box")
+        );
+        assert_eq!(
+            "clean apple: You take it.\nknife: You take it.",
+            run(&verbs, &mut scene, "take everything but the red apple")
+        );
+        assert_eq!(
+            "clean apple: You drop it.\nknife: You drop it.",
+            run(&verbs, &mut scene, "drop them")
+        );
+        assert_eq!(
+            "apple: Which do you mean, the red apple or the clean apple?\n\
+knife: You can't eat it.",
+            run(&verbs, &mut scene, "eat the apple and the knife")
+        );
+    }
+
+    #[test]
+    fn test_open_and_custom_verbs() {
+        let mut verbs = Verbs::new();
+        let mut scene = Scene::from_value(&json!({
+            "children": [{"noun": "box", "is": ["openable"]}]
+        }))
+        .unwrap();
+        assert_eq!(
+            "You open the box.",
+            run(&verbs, &mut scene, "open the box")
+        );
+        assert!(scene.ask_question(
+            &Sentence::from_lang(&make_lang(), "is the box open").unwrap()
+        ));
+        assert_eq!(
+            "The box is already open.",
+            run(&verbs, &mut scene, "open it")
+        );
+
+        assert!(!verbs.knows("kick"));
+        assert_eq!(
+            "You don't know how to kick.",
+            run(&verbs, &mut scene, "kick the box")
+        );
+        verbs.register("kick", |scene, action| {
+            let kicked = scene.remove(action.subject);
+            format!("You kick {} away.", definite(&kicked.unwrap()))
+        });
+        assert_eq!(
+            "You kick the box away.",
+            run(&verbs, &mut scene, "kick the box")
+        );
+        assert_eq!(
+            "You don't see any box here.",
+            run(&verbs, &mut scene, "examine the box")
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use diesel::ConnectionError;
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// QuerierError describes every way a querier operation can fail.
+#[derive(Debug)]
+pub enum QuerierError {
+    /// No row in the table has the given primary key.
+    NotFound {
+        /// The table that was searched.
+        table: &'static str,
+        /// The primary key that was looked up.
+        key: String,
+    },
+    /// A row with the same primary key already exists in the table.
+    DuplicateKey {
+        /// The table the insert or update targeted.
+        table: &'static str,
+        /// The primary key that collided, if a single row was written.
+        key: Option<String>,
+    },
+    /// A JSON formatted column could not be parsed.
+    MalformedJson {
+        /// The column holding the bad JSON.
+        column: &'static str,
+        /// The underlying parse error.
+        source: serde_json::Error,
+    },
+    /// A file or path could not be read.
+    Io(io::Error),
+    /// A JSON dump file could not be parsed.
+    ParseJson(serde_json::Error),
+    /// A TOML dump file could not be parsed.
+    ParseToml(toml::de::Error),
+    /// The sqlite3 db could not be opened.
+    Connection(ConnectionError),
+    /// Any other error reported by the database.
+    Database(DieselError),
+}
+
+impl QuerierError {
+    /// Converts a diesel error into a QuerierError with the table and key it concerns.
+    ///
+    /// # Arguements
+    ///
+    /// * `table` - The table the query ran against.
+    /// * `key` - The primary key of the row queried, if there was a single one.
+    /// * `error` - The error reported by diesel.
+    pub(crate) fn from_diesel(
+        table: &'static str,
+        key: Option<String>,
+        error: DieselError,
+    ) -> QuerierError {
+        match error {
+            DieselError::NotFound => QuerierError::NotFound {
+                table,
+                key: key.unwrap_or_default(),
+            },
+            DieselError::DatabaseError(
+                DatabaseErrorKind::UniqueViolation,
+                _,
+            ) => QuerierError::DuplicateKey { table, key },
+            error => QuerierError::Database(error),
+        }
+    }
+
+    /// Turns a delete or update that changed no rows into NotFound, since
+    /// diesel reports those as succeeding.
+    ///
+    /// # Arguements
+    ///
+    /// * `table` - The table the query ran against.
+    /// * `key` - The primary key the query looked for.
+    /// * `rows` - How many rows the query changed.
+    pub(crate) fn expect_rows(
+        table: &'static str,
+        key: String,
+        rows: usize,
+    ) -> Result<usize, QuerierError> {
+        if rows == 0 {
+            Err(QuerierError::NotFound { table, key })
+        } else {
+            Ok(rows)
+        }
+    }
+}
+
+impl fmt::Display for QuerierError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            QuerierError::NotFound { table, key } => {
+                write!(fmt, "No such entry \"{}\" in {}.", key, table)
+            }
+            QuerierError::DuplicateKey {
+                table,
+                key: Some(key),
+            } => write!(fmt, "Entry \"{}\" already exists in {}.", key, table),
+            QuerierError::DuplicateKey { table, key: None } => {
+                write!(fmt, "Duplicate entry in {}.", table)
+            }
+            QuerierError::MalformedJson { column, source } => {
+                write!(fmt, "Malformed JSON in {} column: {}", column, source)
+            }
+            QuerierError::Io(error) => write!(fmt, "I/O error: {}", error),
+            QuerierError::ParseJson(error) => {
+                write!(fmt, "Failed to parse JSON file: {}", error)
+            }
+            QuerierError::ParseToml(error) => {
+                write!(fmt, "Failed to parse TOML file: {}", error)
+            }
+            QuerierError::Connection(error) => {
+                write!(fmt, "Failed to connect to db: {}", error)
+            }
+            QuerierError::Database(error) => {
+                write!(fmt, "Database error: {}", error)
+            }
+        }
+    }
+}
+
+impl Error for QuerierError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            QuerierError::MalformedJson { source, .. } => Some(source),
+            QuerierError::Io(error) => Some(error),
+            QuerierError::ParseJson(error) => Some(error),
+            QuerierError::ParseToml(error) => Some(error),
+            QuerierError::Connection(error) => Some(error),
+            QuerierError::Database(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for QuerierError {
+    fn from(error: io::Error) -> QuerierError {
+        QuerierError::Io(error)
+    }
+}
+
+impl From<ConnectionError> for QuerierError {
+    fn from(error: ConnectionError) -> QuerierError {
+        QuerierError::Connection(error)
+    }
+}
+
+impl From<DieselError> for QuerierError {
+    fn from(error: DieselError) -> QuerierError {
+        QuerierError::Database(error)
+    }
+}
@@ -6,6 +6,7 @@ extern crate diesel;
 extern crate toml;
 
 pub mod dialogue_tree;
+pub mod error;
 pub mod models;
 pub mod querier;
 mod schema;
@@ -28,7 +29,7 @@ mod tests {
         let querier = models::Querier::new_file(&get_file_path(db_name))
             .expect("Failed to create valid db.");
 
-        querier.setup_db();
+        querier.setup_db().expect("Failed to setup db.");
 
         querier
             .dump_from_file(
@@ -96,14 +97,14 @@ mod tests {
     fn db_create_new_db() {
         let querier = models::Querier::new_file("new_db.db");
 
-        assert!(!querier.is_none());
+        assert!(querier.is_ok());
     }
 
     #[test]
     fn test_basic_query_items() {
         let querier = new_valid_db("query_items.db");
 
-        let items = querier.query_items(Some("apple"), None, None);
+        let items = querier.query_items(Some("apple"), None, None).unwrap();
         assert_eq!(3, items.len());
     }
 
@@ -112,11 +113,11 @@ mod tests {
         let querier = new_valid_db("attr_query_items.db");
 
         let attrs = vec!["red"];
-        let items = querier.query_items(None, Some(attrs), None);
+        let items = querier.query_items(None, Some(attrs), None).unwrap();
         assert_eq!(2, items.len());
 
         let attrs = vec!["fairy", "poisoned"];
-        let items = querier.query_items(None, Some(attrs), None);
+        let items = querier.query_items(None, Some(attrs), None).unwrap();
         assert_eq!(1, items.len());
     }
 
@@ -125,11 +126,11 @@ mod tests {
         let querier = new_valid_db("comp_query_items.db");
 
         let comps = vec!["damages"];
-        let items = querier.query_items(None, None, Some(comps));
+        let items = querier.query_items(None, None, Some(comps)).unwrap();
         assert_eq!(3, items.len());
 
         let comps = vec!["heals", "interactable"];
-        let items = querier.query_items(None, None, Some(comps));
+        let items = querier.query_items(None, None, Some(comps)).unwrap();
         assert_eq!(2, items.len());
     }
 
@@ -137,7 +138,7 @@ mod tests {
     fn test_query_all_items() {
         let querier = new_valid_db("query_all_items.db");
 
-        let items = querier.query_items(None, None, None);
+        let items = querier.query_items(None, None, None).unwrap();
         assert_eq!(6, items.len());
     }
 
@@ -151,10 +152,10 @@ mod tests {
             components: Some(String::from("{'test': 'test'}")),
         };
 
-        let inserted = querier.insert_item(item.clone());
+        let inserted = querier.insert_item(item.clone()).unwrap();
         assert_eq!(1, inserted);
 
-        let got_item = querier.get_item("Test_Item_Insert");
+        let got_item = querier.get_item("Test_Item_Insert").unwrap();
         assert_eq!(item, got_item);
     }
 
@@ -177,45 +178,81 @@ mod tests {
             components: Some(String::from("{'test': 'test'}")),
         });
 
-        let inserted = querier.insert_items(items.clone());
+        let inserted = querier.insert_items(items.clone()).unwrap();
         assert_eq!(2, inserted);
 
-        let q_items = querier.query_items(Some("Test_Item_Insert"), None, None);
+        let q_items = querier
+            .query_items(Some("Test_Item_Insert"), None, None)
+            .unwrap();
         assert_eq!(items[0], q_items[0]);
         assert_eq!(items[1], q_items[1]);
     }
 
     #[test]
-    #[should_panic(
-        expected = "Error inserting item.: DatabaseError(UniqueViolation, \"UNIQUE constraint failed: items.name\")"
-    )]
     fn test_insert_existing_item() {
         let querier = new_valid_db("insert_existing_item.db");
 
-        querier.insert_item(common_item());
+        match querier.insert_item(common_item()) {
+            Err(error::QuerierError::DuplicateKey { table, key }) => {
+                assert_eq!("items", table);
+                assert_eq!(Some(String::from("Test_Item")), key);
+            }
+            other => panic!("Expected DuplicateKey, got {:?}", other),
+        }
     }
 
     #[test]
     fn test_get_item() {
         let querier = new_valid_db("get_item.db");
 
-        let got_item = querier.get_item("Test_Item");
+        let got_item = querier.get_item("Test_Item").unwrap();
         assert_eq!(common_item(), got_item);
     }
 
     #[test]
-    #[should_panic(expected = "Failed to get item.: NotFound")]
     fn test_get_nonexistant_item() {
         let querier = new_valid_db("get_nonexistant_item.db");
 
-        querier.get_item("Fake_Item");
+        match querier.get_item("Fake_Item") {
+            Err(error::QuerierError::NotFound { table, key }) => {
+                assert_eq!("items", table);
+                assert_eq!("Fake_Item", key);
+            }
+            other => panic!("Expected NotFound, got {:?}", other),
+        }
     }
 
     #[test]
     fn test_remove_item() {
         let querier = new_valid_db("remove_item.db");
 
-        assert_eq!(1, querier.remove_item("Test_Item"));
+        assert_eq!(1, querier.remove_item("Test_Item").unwrap());
+    }
+
+    #[test]
+    fn test_remove_nonexistant_item() {
+        let querier = new_valid_db("remove_nonexistant_item.db");
+
+        match querier.remove_item("Fake_Item") {
+            Err(error::QuerierError::NotFound { table, key }) => {
+                assert_eq!("items", table);
+                assert_eq!("Fake_Item", key);
+            }
+            other => panic!("Expected NotFound, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_update_nonexistant_item() {
+        let querier = new_valid_db("update_nonexistant_item.db");
+
+        match querier.update_item("Fake_Item", common_item()) {
+            Err(error::QuerierError::NotFound { table, key }) => {
+                assert_eq!("items", table);
+                assert_eq!("Fake_Item", key);
+            }
+            other => panic!("Expected NotFound, got {:?}", other),
+        }
     }
 
     #[test]
@@ -224,9 +261,9 @@ mod tests {
         let mut item = common_item();
         item.description = Some(String::from("updated description."));
 
-        assert_eq!(1, querier.update_item("Test_Item", item.clone()));
+        assert_eq!(1, querier.update_item("Test_Item", item.clone()).unwrap());
 
-        let got_item = querier.get_item("Test_Item");
+        let got_item = querier.get_item("Test_Item").unwrap();
         assert_eq!(item.clone(), got_item);
     }
 
@@ -239,17 +276,42 @@ mod tests {
         item.attributes = Some(String::from("updates,test"));
         item.components = Some(String::from("{\"updated\": true}"));
 
-        assert_eq!(1, querier.update_item("Test_Item", item.clone()));
+        assert_eq!(1, querier.update_item("Test_Item", item.clone()).unwrap());
 
-        let got_item = querier.get_item("Updated_Item_Name");
+        let got_item = querier.get_item("Updated_Item_Name").unwrap();
         assert_eq!(item.clone(), got_item);
     }
 
+    #[test]
+    fn test_malformed_item_components() {
+        match common_item().components() {
+            Err(error::QuerierError::MalformedJson { column, .. }) => {
+                assert_eq!("components", column)
+            }
+            other => panic!("Expected MalformedJson, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_dump_from_missing_file() {
+        let querier = new_valid_db("dump_from_missing_file.db");
+
+        match querier.dump_from_file(
+            &get_file_path("missing_dump.json"),
+            models::FileType::JSON,
+        ) {
+            Err(error::QuerierError::Io(_)) => (),
+            other => panic!("Expected Io, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_basic_query_locations() {
         let querier = new_valid_db("query_locations.db");
 
-        let locations = querier.query_locations(Some("kitchen"), None, None);
+        let locations = querier
+            .query_locations(Some("kitchen"), None, None)
+            .unwrap();
         assert_eq!(2, locations.len());
     }
 
@@ -258,11 +320,13 @@ mod tests {
         let querier = new_valid_db("item_query_locations.db");
 
         let items = vec!["apple_json"];
-        let locations = querier.query_locations(None, Some(items), None);
+        let locations =
+            querier.query_locations(None, Some(items), None).unwrap();
         assert_eq!(1, locations.len());
 
         let items = vec!["fairy", "Blob"];
-        let locations = querier.query_locations(None, Some(items), None);
+        let locations =
+            querier.query_locations(None, Some(items), None).unwrap();
         assert_eq!(1, locations.len());
     }
 
@@ -271,11 +335,13 @@ mod tests {
         let querier = new_valid_db("chara_query_locations.db");
 
         let charas = vec!["Bird"];
-        let locations = querier.query_locations(None, None, Some(charas));
+        let locations =
+            querier.query_locations(None, None, Some(charas)).unwrap();
         assert_eq!(1, locations.len());
 
         let charas = vec!["mother", "sister"];
-        let locations = querier.query_locations(None, None, Some(charas));
+        let locations =
+            querier.query_locations(None, None, Some(charas)).unwrap();
         assert_eq!(1, locations.len());
     }
 
@@ -283,7 +349,7 @@ mod tests {
     fn test_query_all_locations() {
         let querier = new_valid_db("query_all_locations.db");
 
-        let locations = querier.query_locations(None, None, None);
+        let locations = querier.query_locations(None, None, None).unwrap();
         assert_eq!(6, locations.len());
     }
 
@@ -291,15 +357,17 @@ mod tests {
     fn test_insert_location() {
         let querier = new_valid_db("insert_location.db");
 
-        let inserted = querier.insert_location(models::Location {
-            name: String::from("Test_Location_Insert"),
-            description: Some(String::from(
-                "Test location for insert testing.",
-            )),
-            items: Some(String::from("random string")),
-            neighbors: Some(String::from("vitae")),
-            characters: Some(String::from("umbra")),
-        });
+        let inserted = querier
+            .insert_location(models::Location {
+                name: String::from("Test_Location_Insert"),
+                description: Some(String::from(
+                    "Test location for insert testing.",
+                )),
+                items: Some(String::from("random string")),
+                neighbors: Some(String::from("vitae")),
+                characters: Some(String::from("umbra")),
+            })
+            .unwrap();
         assert_eq!(1, inserted);
     }
 
@@ -328,46 +396,55 @@ mod tests {
             characters: Some(String::from("umbra")),
         });
 
-        let inserted = querier.insert_locations(locations.clone());
+        let inserted = querier.insert_locations(locations.clone()).unwrap();
         assert_eq!(2, inserted);
 
-        let q_locations =
-            querier.query_locations(Some("Test_Location_Insert"), None, None);
+        let q_locations = querier
+            .query_locations(Some("Test_Location_Insert"), None, None)
+            .unwrap();
         assert_eq!(locations[0], q_locations[0]);
         assert_eq!(locations[1], q_locations[1]);
     }
 
     #[test]
-    #[should_panic(
-        expected = "Error inserting location.: DatabaseError(UniqueViolation, \"UNIQUE constraint failed: locations.name\")"
-    )]
     fn test_insert_existing_location() {
         let querier = new_valid_db("insert_existing_location.db");
 
-        querier.insert_location(common_location());
+        match querier.insert_location(common_location()) {
+            Err(error::QuerierError::DuplicateKey { table, key }) => {
+                assert_eq!("locations", table);
+                assert_eq!(Some(String::from("Test_Location")), key);
+            }
+            other => panic!("Expected DuplicateKey, got {:?}", other),
+        }
     }
 
     #[test]
     fn test_get_location() {
         let querier = new_valid_db("get_location.db");
 
-        let got_location = querier.get_location("Test_Location");
+        let got_location = querier.get_location("Test_Location").unwrap();
         assert_eq!(got_location, common_location());
     }
 
     #[test]
-    #[should_panic(expected = "Failed to get location.: NotFound")]
     fn test_get_nonexistant_location() {
         let querier = new_valid_db("get_nonexistant_location.db");
 
-        querier.get_location("Fake_Location");
+        match querier.get_location("Fake_Location") {
+            Err(error::QuerierError::NotFound { table, key }) => {
+                assert_eq!("locations", table);
+                assert_eq!("Fake_Location", key);
+            }
+            other => panic!("Expected NotFound, got {:?}", other),
+        }
     }
 
     #[test]
     fn test_remove_location() {
         let querier = new_valid_db("remove_location.db");
 
-        assert_eq!(1, querier.remove_location("Test_Location"));
+        assert_eq!(1, querier.remove_location("Test_Location").unwrap());
     }
 
     #[test]
@@ -378,10 +455,12 @@ mod tests {
 
         assert_eq!(
             1,
-            querier.update_location("Test_Location", location.clone())
+            querier
+                .update_location("Test_Location", location.clone())
+                .unwrap()
         );
 
-        let got_location = querier.get_location("Test_Location");
+        let got_location = querier.get_location("Test_Location").unwrap();
         assert_eq!(location.clone(), got_location);
     }
 
@@ -397,10 +476,13 @@ mod tests {
 
         assert_eq!(
             1,
-            querier.update_location("Test_Location", location.clone())
+            querier
+                .update_location("Test_Location", location.clone())
+                .unwrap()
         );
 
-        let got_location = querier.get_location("Updated_Location_Name");
+        let got_location =
+            querier.get_location("Updated_Location_Name").unwrap();
         assert_eq!(location.clone(), got_location);
     }
 
@@ -408,7 +490,7 @@ mod tests {
     fn test_basic_query_characters() {
         let querier = new_valid_db("query_characters.db");
 
-        let characters = querier.query_characters(Some("dad"), None);
+        let characters = querier.query_characters(Some("dad"), None).unwrap();
         assert_eq!(2, characters.len());
     }
 
@@ -417,11 +499,11 @@ mod tests {
         let querier = new_valid_db("comp_query_characters.db");
 
         let comps = vec!["interactable"];
-        let locations = querier.query_characters(None, Some(comps));
+        let locations = querier.query_characters(None, Some(comps)).unwrap();
         assert_eq!(5, locations.len());
 
         let comps = vec!["interactable", "killable"];
-        let locations = querier.query_characters(None, Some(comps));
+        let locations = querier.query_characters(None, Some(comps)).unwrap();
         assert_eq!(1, locations.len());
     }
 
@@ -429,7 +511,7 @@ mod tests {
     fn test_query_all_characters() {
         let querier = new_valid_db("query_characters.db");
 
-        let characters = querier.query_characters(None, None);
+        let characters = querier.query_characters(None, None).unwrap();
         assert_eq!(5, characters.len());
     }
 
@@ -437,10 +519,12 @@ mod tests {
     fn test_insert_character() {
         let querier = new_valid_db("insert_character.db");
 
-        let inserted = querier.insert_character(models::Character {
-            name: String::from("Test_Character_Insert"),
-            components: Some(String::from("{ \"interactable\": true }")),
-        });
+        let inserted = querier
+            .insert_character(models::Character {
+                name: String::from("Test_Character_Insert"),
+                components: Some(String::from("{ \"interactable\": true }")),
+            })
+            .unwrap();
         assert_eq!(1, inserted);
     }
 
@@ -459,46 +543,55 @@ mod tests {
             components: Some(String::from("{ \"interactable\": true }")),
         });
 
-        let inserted = querier.insert_characters(characters.clone());
+        let inserted = querier.insert_characters(characters.clone()).unwrap();
         assert_eq!(2, inserted);
 
-        let q_characters =
-            querier.query_characters(Some("Test_Character_Insert"), None);
+        let q_characters = querier
+            .query_characters(Some("Test_Character_Insert"), None)
+            .unwrap();
         assert_eq!(characters[0], q_characters[0]);
         assert_eq!(characters[1], q_characters[1]);
     }
 
     #[test]
-    #[should_panic(
-        expected = "Error inserting character.: DatabaseError(UniqueViolation, \"UNIQUE constraint failed: characters.name\")"
-    )]
     fn test_insert_existing_character() {
         let querier = new_valid_db("insert_existing_character.db");
 
-        querier.insert_character(common_character());
+        match querier.insert_character(common_character()) {
+            Err(error::QuerierError::DuplicateKey { table, key }) => {
+                assert_eq!("characters", table);
+                assert_eq!(Some(String::from("Test_Character")), key);
+            }
+            other => panic!("Expected DuplicateKey, got {:?}", other),
+        }
     }
 
     #[test]
     fn test_get_character() {
         let querier = new_valid_db("get_character.db");
 
-        let got_character = querier.get_character("Test_Character");
+        let got_character = querier.get_character("Test_Character").unwrap();
         assert_eq!(got_character, common_character());
     }
 
     #[test]
-    #[should_panic(expected = "Failed to get character.: NotFound")]
     fn test_get_nonexistant_character() {
         let querier = new_valid_db("get_nonexistant_character.db");
 
-        querier.get_character("Fake_Character");
+        match querier.get_character("Fake_Character") {
+            Err(error::QuerierError::NotFound { table, key }) => {
+                assert_eq!("characters", table);
+                assert_eq!("Fake_Character", key);
+            }
+            other => panic!("Expected NotFound, got {:?}", other),
+        }
     }
 
     #[test]
     fn test_remove_character() {
         let querier = new_valid_db("remove_character.db");
 
-        assert_eq!(1, querier.remove_character("Test_Character"));
+        assert_eq!(1, querier.remove_character("Test_Character").unwrap());
     }
 
     #[test]
@@ -509,10 +602,12 @@ mod tests {
 
         assert_eq!(
             1,
-            querier.update_character("Test_Character", character.clone())
+            querier
+                .update_character("Test_Character", character.clone())
+                .unwrap()
         );
 
-        let got_character = querier.get_character("Test_Character");
+        let got_character = querier.get_character("Test_Character").unwrap();
         assert_eq!(character.clone(), got_character);
     }
 
@@ -525,10 +620,13 @@ mod tests {
 
         assert_eq!(
             1,
-            querier.update_character("Test_Character", character.clone())
+            querier
+                .update_character("Test_Character", character.clone())
+                .unwrap()
         );
 
-        let got_character = querier.get_character("Updated_Character_Name");
+        let got_character =
+            querier.get_character("Updated_Character_Name").unwrap();
         assert_eq!(character.clone(), got_character);
     }
 
@@ -537,8 +635,9 @@ mod tests {
         let querier = new_valid_db("query_dialogues.db");
 
         let characters = vec!["dad"];
-        let dialogues =
-            querier.query_dialogues(Some(characters), None, None, None);
+        let dialogues = querier
+            .query_dialogues(Some(characters), None, None, None)
+            .unwrap();
         assert_eq!(2, dialogues.len());
     }
 
@@ -547,11 +646,15 @@ mod tests {
         let querier = new_valid_db("flag_query_dialogues.db");
 
         let flags = vec!["grounded"];
-        let dialogues = querier.query_dialogues(None, Some(flags), None, None);
+        let dialogues = querier
+            .query_dialogues(None, Some(flags), None, None)
+            .unwrap();
         assert_eq!(2, dialogues.len());
 
         let flags = vec!["fairy", "poisoned"];
-        let dialogues = querier.query_dialogues(None, Some(flags), None, None);
+        let dialogues = querier
+            .query_dialogues(None, Some(flags), None, None)
+            .unwrap();
         assert_eq!(1, dialogues.len());
     }
 
@@ -559,8 +662,9 @@ mod tests {
     fn test_loc_query_dialogues() {
         let querier = new_valid_db("comp_query_dialogues.db");
 
-        let dialogues =
-            querier.query_dialogues(None, None, Some("kitchen"), None);
+        let dialogues = querier
+            .query_dialogues(None, None, Some("kitchen"), None)
+            .unwrap();
         assert_eq!(2, dialogues.len());
     }
 
@@ -569,11 +673,15 @@ mod tests {
         let querier = new_valid_db("snip_query_dialogues.db");
 
         let snips = vec!["I wanna die"];
-        let dialogues = querier.query_dialogues(None, None, None, Some(snips));
+        let dialogues = querier
+            .query_dialogues(None, None, None, Some(snips))
+            .unwrap();
         assert_eq!(1, dialogues.len());
 
         let snips = vec!["I wanna die", "Me too thanks"];
-        let dialogues = querier.query_dialogues(None, None, None, Some(snips));
+        let dialogues = querier
+            .query_dialogues(None, None, None, Some(snips))
+            .unwrap();
         assert_eq!(1, dialogues.len());
     }
 
@@ -581,7 +689,8 @@ mod tests {
     fn test_query_all_dialogues() {
         let querier = new_valid_db("query_all_dialogues.db");
 
-        let dialogues = querier.query_dialogues(None, None, None, None);
+        let dialogues =
+            querier.query_dialogues(None, None, None, None).unwrap();
         assert_eq!(6, dialogues.len());
     }
 
@@ -597,10 +706,10 @@ mod tests {
             dialogue: String::from("{\"story\":[{\"who\": \"Blob\", \"what\": \"Hello! I am Blob.\"}],\"choices\": null,\"visited\":false}"),
         };
 
-        let inserted = querier.insert_dialogue(dialogue.clone());
+        let inserted = querier.insert_dialogue(dialogue.clone()).unwrap();
         assert_eq!(1, inserted);
 
-        let got_dialogue = querier.get_dialogue(50);
+        let got_dialogue = querier.get_dialogue(50).unwrap();
         assert_eq!(dialogue, got_dialogue);
     }
 
@@ -627,47 +736,56 @@ mod tests {
             dialogue: String::from("{\"story\":[{\"who\": \"Blob\", \"what\": \"Pew pew pew!\"}],\"choices\": null,\"visited\":false}"),
         });
 
-        let inserted = querier.insert_dialogues(dialogues.clone());
+        let inserted = querier.insert_dialogues(dialogues.clone()).unwrap();
         assert_eq!(2, inserted);
 
         let characters = vec!["blob"];
-        let q_dialogues =
-            querier.query_dialogues(Some(characters), None, None, None);
+        let q_dialogues = querier
+            .query_dialogues(Some(characters), None, None, None)
+            .unwrap();
         assert_eq!(dialogues[0], q_dialogues[0]);
         assert_eq!(dialogues[1], q_dialogues[1]);
     }
 
     #[test]
-    #[should_panic(
-        expected = "Error inserting dialogue.: DatabaseError(UniqueViolation, \"UNIQUE constraint failed: dialogues.id\")"
-    )]
     fn test_insert_existing_dialogue() {
         let querier = new_valid_db("insert_existing_dialogue.db");
 
-        querier.insert_dialogue(common_dialogue());
+        match querier.insert_dialogue(common_dialogue()) {
+            Err(error::QuerierError::DuplicateKey { table, key }) => {
+                assert_eq!("dialogues", table);
+                assert_eq!(Some(String::from("100")), key);
+            }
+            other => panic!("Expected DuplicateKey, got {:?}", other),
+        }
     }
 
     #[test]
     fn test_get_dialogue() {
         let querier = new_valid_db("get_dialogue.db");
 
-        let got_dialogue = querier.get_dialogue(100);
+        let got_dialogue = querier.get_dialogue(100).unwrap();
         assert_eq!(common_dialogue(), got_dialogue);
     }
 
     #[test]
-    #[should_panic(expected = "Failed to get dialogue.: NotFound")]
     fn test_get_nonexistant_dialogue() {
         let querier = new_valid_db("get_nonexistant_dialogue.db");
 
-        querier.get_dialogue(1000);
+        match querier.get_dialogue(1000) {
+            Err(error::QuerierError::NotFound { table, key }) => {
+                assert_eq!("dialogues", table);
+                assert_eq!("1000", key);
+            }
+            other => panic!("Expected NotFound, got {:?}", other),
+        }
     }
 
     #[test]
     fn test_remove_dialogue() {
         let querier = new_valid_db("remove_dialogue.db");
 
-        assert_eq!(1, querier.remove_dialogue(100));
+        assert_eq!(1, querier.remove_dialogue(100).unwrap());
     }
 
     #[test]
@@ -676,9 +794,9 @@ mod tests {
         let mut dialogue = common_dialogue();
         dialogue.dialogue = String::from("updated dialogue.");
 
-        assert_eq!(1, querier.update_dialogue(100, dialogue.clone()));
+        assert_eq!(1, querier.update_dialogue(100, dialogue.clone()).unwrap());
 
-        let got_dialogue = querier.get_dialogue(100);
+        let got_dialogue = querier.get_dialogue(100).unwrap();
         assert_eq!(dialogue.clone(), got_dialogue);
     }
 
@@ -691,9 +809,9 @@ mod tests {
         dialogue.location = String::from("Test_Location");
         dialogue.dialogue = String::from("{\"story\":[{\"who\": \"mario\", \"what\": \"Mama Mia\"}],\"choices\": null,\"visited\":false}");
 
-        assert_eq!(1, querier.update_dialogue(100, dialogue.clone()));
+        assert_eq!(1, querier.update_dialogue(100, dialogue.clone()).unwrap());
 
-        let got_dialogue = querier.get_dialogue(100);
+        let got_dialogue = querier.get_dialogue(100).unwrap();
         assert_eq!(dialogue.clone(), got_dialogue);
     }
 
@@ -701,7 +819,7 @@ mod tests {
     fn test_dialogue_dialogue_tree_conversion() {
         let dialogue = common_dialogue();
 
-        assert_eq!(dialogue.dialogue(), serde_json::from_str("{\"story\":[],\"choices\": [{\"what\":\"What's for dinner y'all?\",\"next\":4},{\"what\":\"How can I help set up?\",\"next\":5}],\"visited\":false}").unwrap());
+        assert_eq!(dialogue.dialogue().unwrap(), serde_json::from_str("{\"story\":[],\"choices\": [{\"what\":\"What's for dinner y'all?\",\"next\":4},{\"what\":\"How can I help set up?\",\"next\":5}],\"visited\":false}").unwrap());
     }
 
     #[test]
@@ -712,10 +830,10 @@ mod tests {
             data: String::from("Test_Node_Insert"),
         };
 
-        let inserted = querier.insert_node(node.clone());
+        let inserted = querier.insert_node(node.clone()).unwrap();
         assert_eq!(1, inserted);
 
-        let got_node = querier.get_node(50);
+        let got_node = querier.get_node(50).unwrap();
         assert_eq!(node, got_node);
     }
 
@@ -734,39 +852,47 @@ mod tests {
             data: String::from("Test_Node_Insert_2"),
         });
 
-        let inserted = querier.insert_nodes(nodes.clone());
+        let inserted = querier.insert_nodes(nodes.clone()).unwrap();
         assert_eq!(2, inserted);
 
-        let node_101 = querier.get_node(101);
-        let node_102 = querier.get_node(102);
+        let node_101 = querier.get_node(101).unwrap();
+        let node_102 = querier.get_node(102).unwrap();
         assert_eq!(nodes[0], node_101);
         assert_eq!(nodes[1], node_102);
     }
 
     #[test]
-    #[should_panic(
-        expected = "Error inserting node.: DatabaseError(UniqueViolation, \"UNIQUE constraint failed: nodes.id\")"
-    )]
     fn test_insert_existing_node() {
         let querier = new_valid_db("insert_existing_node.db");
 
-        querier.insert_node(common_node());
+        match querier.insert_node(common_node()) {
+            Err(error::QuerierError::DuplicateKey { table, key }) => {
+                assert_eq!("nodes", table);
+                assert_eq!(Some(String::from("100")), key);
+            }
+            other => panic!("Expected DuplicateKey, got {:?}", other),
+        }
     }
 
     #[test]
     fn test_get_node() {
         let querier = new_valid_db("get_node.db");
 
-        let got_node = querier.get_node(100);
+        let got_node = querier.get_node(100).unwrap();
         assert_eq!(common_node(), got_node);
     }
 
     #[test]
-    #[should_panic(expected = "Failed to get node.: NotFound")]
     fn test_get_nonexistant_node() {
         let querier = new_valid_db("get_nonexistant_node.db");
 
-        querier.get_node(666);
+        match querier.get_node(666) {
+            Err(error::QuerierError::NotFound { table, key }) => {
+                assert_eq!("nodes", table);
+                assert_eq!("666", key);
+            }
+            other => panic!("Expected NotFound, got {:?}", other),
+        }
     }
 
     #[test]
@@ -775,16 +901,31 @@ mod tests {
         let mut node = common_node();
         node.data = String::from("updated data.");
 
-        assert_eq!(1, querier.update_node(100, node.clone()));
+        assert_eq!(1, querier.update_node(100, node.clone()).unwrap());
 
-        let got_node = querier.get_node(100);
+        let got_node = querier.get_node(100).unwrap();
         assert_eq!(node.clone(), got_node);
     }
 
+    #[test]
+    fn test_malformed_node_data() {
+        let node = models::Node {
+            id: 0,
+            data: String::from("not json"),
+        };
+
+        match node.to_struct() {
+            Err(error::QuerierError::MalformedJson { column, .. }) => {
+                assert_eq!("data", column)
+            }
+            other => panic!("Expected MalformedJson, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_node_dialogue_tree_conversion() {
         let node = common_node();
 
-        assert_eq!(node.to_struct(), serde_json::from_str("{\"story\":[{\"what\":\"Mama milk?\",\"who\": \"dad\"},{\"what\":\"Really dad?\",\"who\": \"sister\"}],\"choices\": null,\"visited\":false}").unwrap());
+        assert_eq!(node.to_struct().unwrap(), serde_json::from_str("{\"story\":[{\"what\":\"Mama milk?\",\"who\": \"dad\"},{\"what\":\"Really dad?\",\"who\": \"sister\"}],\"choices\": null,\"visited\":false}").unwrap());
     }
 }
@@ -4,6 +4,7 @@ use diesel::sqlite::SqliteConnection;
 use serde::Deserialize;
 
 use dialogue_tree::*;
+use error::QuerierError;
 
 /// FileType to describe a type of file.
 pub enum FileType {
@@ -30,6 +31,15 @@ pub struct Item {
     pub components: Option<String>,
 }
 
+impl Item {
+    /// Returns the components field of the struct as a JSON value, if it is set.
+    pub fn components(
+        &self,
+    ) -> Result<Option<serde_json::Value>, QuerierError> {
+        parse_components(&self.components)
+    }
+}
+
 #[derive(Insertable, Queryable, Clone, Debug, Deserialize, PartialEq)]
 #[table_name = "locations"]
 /// Location is struct to contain all information about a location.
@@ -52,7 +62,7 @@ impl Location {
     /// # Arguements
     ///
     /// * `querier` - A querier object to query the items table.
-    pub fn items(self, querier: Querier) -> Vec<Item> {
+    pub fn items(self, querier: Querier) -> Result<Vec<Item>, QuerierError> {
         use crate::schema::items::dsl::*;
         let connection = querier.connection;
         let mut items_in_room: Vec<Item> = Vec::new();
@@ -61,16 +71,21 @@ impl Location {
             for item in items_string.split(",") {
                 if !item.is_empty() {
                     items_in_room.push(
-                        items
-                            .find(item)
-                            .first(&connection)
-                            .expect("Could not look up item."),
+                        items.find(item).first(&connection).map_err(
+                            |error| {
+                                QuerierError::from_diesel(
+                                    "items",
+                                    Some(item.to_string()),
+                                    error,
+                                )
+                            },
+                        )?,
                     );
                 }
             }
         }
 
-        items_in_room
+        Ok(items_in_room)
     }
 
     /// Returns a Vector of dialogues from the database based off the location name field.
@@ -78,14 +93,17 @@ impl Location {
     /// # Arguements
     ///
     /// * `querier` - A querier object to query the items table.
-    pub fn dialogues(self, querier: Querier) -> Vec<Dialogue> {
+    pub fn dialogues(
+        self,
+        querier: Querier,
+    ) -> Result<Vec<Dialogue>, QuerierError> {
         use crate::schema::dialogues::dsl::*;
         let connection = querier.connection;
 
         dialogues
             .filter(location.like(format!("%{}%", self.name)))
             .load::<Dialogue>(&connection)
-            .expect("Could not look up dialogues.")
+            .map_err(QuerierError::from)
     }
 }
 
@@ -100,19 +118,29 @@ pub struct Character {
 }
 
 impl Character {
+    /// Returns the components field of the struct as a JSON value, if it is set.
+    pub fn components(
+        &self,
+    ) -> Result<Option<serde_json::Value>, QuerierError> {
+        parse_components(&self.components)
+    }
+
     /// Returns a Vector of dialogues from the database based off the character name field.
     ///
     /// # Arguements
     ///
     /// * `querier` - A querier object to query the items table.
-    pub fn dialogues(self, querier: Querier) -> Vec<Dialogue> {
+    pub fn dialogues(
+        self,
+        querier: Querier,
+    ) -> Result<Vec<Dialogue>, QuerierError> {
         use crate::schema::dialogues::dsl::*;
         let connection = querier.connection;
 
         dialogues
             .filter(characters.like(format!("%{}%", self.name)))
             .load::<Dialogue>(&connection)
-            .expect("Could not look up dialogues.")
+            .map_err(QuerierError::from)
     }
 }
 
@@ -141,8 +169,13 @@ impl Dialogue {
     }
 
     /// Returns the dialogue field of the struct as a StoryNode struct.
-    pub fn dialogue(&self) -> StoryNode {
-        serde_json::from_str(&self.dialogue).unwrap()
+    pub fn dialogue(&self) -> Result<StoryNode, QuerierError> {
+        serde_json::from_str(&self.dialogue).map_err(|error| {
+            QuerierError::MalformedJson {
+                column: "dialogue",
+                source: error,
+            }
+        })
     }
 }
 
@@ -158,7 +191,29 @@ pub struct Node {
 
 impl Node {
     /// Returns the data field of the struct as a StoryNode struct.
-    pub fn to_struct(&self) -> StoryNode {
-        serde_json::from_str(&self.data).unwrap()
+    pub fn to_struct(&self) -> Result<StoryNode, QuerierError> {
+        serde_json::from_str(&self.data).map_err(|error| {
+            QuerierError::MalformedJson {
+                column: "data",
+                source: error,
+            }
+        })
+    }
+}
+
+/// Parses a JSON formatted components column.
+fn parse_components(
+    components: &Option<String>,
+) -> Result<Option<serde_json::Value>, QuerierError> {
+    match components {
+        Some(components) => {
+            serde_json::from_str(components).map(Some).map_err(|error| {
+                QuerierError::MalformedJson {
+                    column: "components",
+                    source: error,
+                }
+            })
+        }
+        None => Ok(None),
     }
 }
@@ -4,8 +4,11 @@ use diesel::*;
 use serde::Deserialize;
 use std::env::current_exe;
 use std::fs::{remove_file, File};
+use std::io;
 use std::io::Read;
+use std::path::PathBuf;
 
+use crate::error::QuerierError;
 use crate::models::*;
 
 #[derive(Deserialize, Debug)]
@@ -23,6 +26,37 @@ struct DataFile {
     nodes: Option<Vec<Node>>,
 }
 
+/// Resolves a file name relative to the directory of the running executable.
+///
+/// # Arguements
+///
+/// * `file_name` - The name of the file relative to the app.
+fn exe_relative_path(file_name: &str) -> Result<PathBuf, QuerierError> {
+    let mut path_buffer = current_exe()?;
+    path_buffer.pop();
+    path_buffer.push(file_name);
+
+    Ok(path_buffer)
+}
+
+/// Converts a path into the string form sqlite3 and diesel expect.
+fn path_to_string(path_buffer: PathBuf) -> Result<String, QuerierError> {
+    path_buffer.into_os_string().into_string().map_err(|path| {
+        QuerierError::Io(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("String conversion of path {:?} failed.", path),
+        ))
+    })
+}
+
+/// Opens a sqlite3 connection to the given path with foreign keys enabled.
+fn connect(path: &str) -> Result<Querier, QuerierError> {
+    let conn = SqliteConnection::establish(path)?;
+    conn.execute("PRAGMA foreign_keys = ON")?;
+
+    Ok(Querier { connection: conn })
+}
+
 impl Querier {
     /// Creates a new querier existance from an existing sqlite3 db relative to the app.
     ///
@@ -36,30 +70,17 @@ impl Querier {
     /// use querier::models::Querier;
     /// let querier = Querier::new("file_name.db");
     /// ```
-    pub fn new(file_name: &str) -> Option<Querier> {
-        let mut path_buffer = current_exe().expect("Failed to get exec path.");
-        path_buffer.pop();
-        path_buffer.push(file_name);
+    pub fn new(file_name: &str) -> Result<Querier, QuerierError> {
+        let path_buffer = exe_relative_path(file_name)?;
 
         if !path_buffer.exists() {
-            return None;
+            return Err(QuerierError::Io(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No db file at {:?}.", path_buffer),
+            )));
         }
 
-        let path = path_buffer
-            .into_os_string()
-            .into_string()
-            .expect("String conversion of db path failed.");
-
-        let conn = SqliteConnection::establish(&path)
-            .unwrap_or_else(|_| panic!("Error connecting to {}", path));
-
-        let res = conn.execute("PRAGMA foreign_keys = ON");
-
-        if res.is_ok() {
-            return Some(Querier { connection: conn });
-        }
-
-        None
+        connect(&path_to_string(path_buffer)?)
     }
 
     /// Creates a new querier and a new db file.
@@ -74,30 +95,14 @@ impl Querier {
     /// use querier::models::Querier;
     /// let querier = Querier::new_file("file_name.db");
     /// ```
-    pub fn new_file(file_name: &str) -> Option<Querier> {
-        let mut path_buffer = current_exe().expect("Failed to get exec path.");
-        path_buffer.pop();
-        path_buffer.push(file_name);
+    pub fn new_file(file_name: &str) -> Result<Querier, QuerierError> {
+        let path_buffer = exe_relative_path(file_name)?;
 
         if path_buffer.exists() {
-            remove_file(&path_buffer).expect("Failed to remove old file.");
-        }
-
-        let path = path_buffer
-            .into_os_string()
-            .into_string()
-            .expect("String conversion of db path failed.");
-
-        let conn = SqliteConnection::establish(&path)
-            .unwrap_or_else(|_| panic!("Error connecting to {}", path));
-
-        let res = conn.execute("PRAGMA foreign_keys = ON");
-
-        if res.is_ok() {
-            return Some(Querier { connection: conn });
+            remove_file(&path_buffer)?;
         }
 
-        None
+        connect(&path_to_string(path_buffer)?)
     }
 
     /// Given a querier instance setup the database tables and indexes.
@@ -106,65 +111,62 @@ impl Querier {
     ///
     /// ```
     /// use querier::models::Querier;
-    /// let querier = Querier::new_file("file_name.db");
-    /// querier.setup_db();
+    /// let querier = Querier::new_file("file_name.db").expect("valid db connection");
+    /// querier.setup_db().expect("Failed to setup db");
     /// ```
-    pub fn setup_db(&self) {
-        sql_query("CREATE TABLE items (name TEXT PRIMARY KEY, description TEXT, attributes TEXT, components TEXT)").execute(&self.connection).expect("Failed to create items table.");
+    pub fn setup_db(&self) -> Result<(), QuerierError> {
+        sql_query("CREATE TABLE items (name TEXT PRIMARY KEY, description TEXT, attributes TEXT, components TEXT)").execute(&self.connection)?;
         sql_query("CREATE UNIQUE INDEX item_names ON items (name)")
-            .execute(&self.connection)
-            .expect("Failed to create items index.");
+            .execute(&self.connection)?;
 
-        sql_query("CREATE TABLE locations (name TEXT PRIMARY KEY, description TEXT, items TEXT, neighbors TEXT, characters TEXT)").execute(&self.connection).expect("Failed to create locations table.");
+        sql_query("CREATE TABLE locations (name TEXT PRIMARY KEY, description TEXT, items TEXT, neighbors TEXT, characters TEXT)").execute(&self.connection)?;
         sql_query("CREATE UNIQUE INDEX location_names ON locations (name)")
-            .execute(&self.connection)
-            .expect("Failed to create locations index.");
+            .execute(&self.connection)?;
 
         sql_query(
             "CREATE TABLE characters (name TEXT PRIMARY KEY, components TEXT)",
         )
-        .execute(&self.connection)
-        .expect("Failed to create characters table.");
+        .execute(&self.connection)?;
         sql_query("CREATE UNIQUE INDEX character_names ON characters (name)")
-            .execute(&self.connection)
-            .expect("Failed to create characters index.");
+            .execute(&self.connection)?;
 
-        sql_query("CREATE TABLE dialogues (id INTEGER PRIMARY KEY, characters TEXT NOT NULL, flags TEXT, location TEXT, priority INTEGER, dialogue TEXT NOT NULL)").execute(&self.connection).expect("Failed to create items table.");
+        sql_query("CREATE TABLE dialogues (id INTEGER PRIMARY KEY, characters TEXT NOT NULL, flags TEXT, location TEXT, priority INTEGER, dialogue TEXT NOT NULL)").execute(&self.connection)?;
         sql_query("CREATE UNIQUE INDEX dialogues_id ON dialogues (id)")
-            .execute(&self.connection)
-            .expect("Failed to create dialogue index.");
+            .execute(&self.connection)?;
 
         sql_query(
             "CREATE TABLE nodes (id INTEGER PRIMARY KEY, data TEXT NOT NULL)",
         )
-        .execute(&self.connection)
-        .expect("Failed to create items table.");
+        .execute(&self.connection)?;
         sql_query("CREATE UNIQUE INDEX nodes_id ON nodes (id)")
-            .execute(&self.connection)
-            .expect("Failed to create node index.");
+            .execute(&self.connection)?;
+
+        Ok(())
     }
 
     /// Given a querier instance setup dup data from the DataFile struct into the db tables.
-    fn dump_data(&self, data: DataFile) {
+    fn dump_data(&self, data: DataFile) -> Result<(), QuerierError> {
         if let Some(items) = data.items {
-            self.insert_items(items);
+            self.insert_items(items)?;
         }
 
         if let Some(locations) = data.locations {
-            self.insert_locations(locations);
+            self.insert_locations(locations)?;
         }
 
         if let Some(characters) = data.characters {
-            self.insert_characters(characters);
+            self.insert_characters(characters)?;
         }
 
         if let Some(dialogues) = data.dialogues {
-            self.insert_dialogues(dialogues);
+            self.insert_dialogues(dialogues)?;
         }
 
         if let Some(nodes) = data.nodes {
-            self.insert_nodes(nodes);
+            self.insert_nodes(nodes)?;
         }
+
+        Ok(())
     }
 
     /// Given a querier instance and a json/toml file, dump the file data to the tables in database.
@@ -186,35 +188,21 @@ impl Querier {
         &self,
         path: &str,
         file_type: FileType,
-    ) -> Result<(), std::io::Error> {
-        let mut path_buffer = current_exe().expect("Failed to get exec path.");
-        path_buffer.pop();
-        path_buffer.push(path);
-
-        let full_path = path_buffer
-            .into_os_string()
-            .into_string()
-            .expect("String conversion of db path failed.");
+    ) -> Result<(), QuerierError> {
+        let full_path = path_to_string(exe_relative_path(path)?)?;
 
         let mut file = File::open(full_path)?;
         let mut file_content = String::new();
-        file.read_to_string(&mut file_content)
-            .expect("Failed to read file into string");
+        file.read_to_string(&mut file_content)?;
 
-        match file_type {
-            FileType::TOML => {
-                let data_file: DataFile = toml::from_str(&file_content)?;
+        let data_file: DataFile = match file_type {
+            FileType::TOML => toml::from_str(&file_content)
+                .map_err(QuerierError::ParseToml)?,
+            FileType::JSON => serde_json::from_str(&file_content)
+                .map_err(QuerierError::ParseJson)?,
+        };
 
-                self.dump_data(data_file);
-            }
-            FileType::JSON => {
-                let data_file: DataFile = serde_json::from_str(&file_content)?;
-
-                self.dump_data(data_file);
-            }
-        }
-
-        Ok(())
+        self.dump_data(data_file)
     }
 
     /// Given a querier instance query items from the database instance. If all arguments are None it queries all items.
@@ -239,7 +227,7 @@ impl Querier {
         name: Option<&str>,
         attributes: Option<Vec<&str>>,
         components: Option<Vec<&str>>,
-    ) -> Vec<Item> {
+    ) -> Result<Vec<Item>, QuerierError> {
         use crate::schema::items;
 
         let mut query = items::table.into_boxed();
@@ -248,7 +236,7 @@ impl Querier {
             return query
                 .filter(items::name.like(format!("%{}%", name)))
                 .load::<Item>(&self.connection)
-                .expect("Error loading items.");
+                .map_err(QuerierError::from);
         }
 
         if let Some(attrs) = attributes {
@@ -267,7 +255,7 @@ impl Querier {
 
         query
             .load::<Item>(&self.connection)
-            .expect("Error loading items.")
+            .map_err(QuerierError::from)
     }
 
     /// Given a querier instance and item name fetch it if it exists.
@@ -285,13 +273,19 @@ impl Querier {
     /// querier.dump_from_file("/path/to/data.json", FileType::JSON).expect("Unsuccesful dump to database");
     /// let item = querier.get_item("Test_Item");
     /// ```
-    pub fn get_item(&self, item_name: &str) -> Item {
+    pub fn get_item(&self, item_name: &str) -> Result<Item, QuerierError> {
         use crate::schema::items::dsl::*;
 
         items
             .find(item_name)
             .get_result::<Item>(&self.connection)
-            .expect("Failed to get item.")
+            .map_err(|error| {
+                QuerierError::from_diesel(
+                    "items",
+                    Some(item_name.to_string()),
+                    error,
+                )
+            })
     }
 
     /// Given a querier instance and item struct to insert into the database instance.
@@ -314,13 +308,19 @@ impl Querier {
     /// components: String::from("{'test': 'test'}"),
     /// });
     /// ```
-    pub fn insert_item(&self, item: Item) -> usize {
+    pub fn insert_item(&self, item: Item) -> Result<usize, QuerierError> {
         use crate::schema::items::dsl::*;
 
         diesel::insert_into(items)
             .values(&item)
             .execute(&self.connection)
-            .expect("Error inserting item.")
+            .map_err(|error| {
+                QuerierError::from_diesel(
+                    "items",
+                    Some(item.name.clone()),
+                    error,
+                )
+            })
     }
 
     /// Given a querier instance and vector of item structs to be inserted into the database instance.
@@ -328,16 +328,19 @@ impl Querier {
     /// # Arguements
     ///
     /// * `items` - The vector of item structs to be inserted.
-    pub fn insert_items(&self, insert_items: Vec<Item>) -> usize {
+    pub fn insert_items(
+        &self,
+        insert_items: Vec<Item>,
+    ) -> Result<usize, QuerierError> {
         use crate::schema::items::dsl::*;
 
         diesel::insert_into(items)
             .values(&insert_items)
             .execute(&self.connection)
-            .expect("Error inserting items.")
+            .map_err(|error| QuerierError::from_diesel("items", None, error))
     }
 
-    /// Given a querier instance and item name remove it if it exists.
+    /// Given a querier instance and item name remove it. Fails with NotFound if it does not exist.
     ///
     /// # Arguements
     ///
@@ -352,15 +355,22 @@ impl Querier {
     /// querier.dump_from_file("/path/to/data.json", FileType::JSON).expect("Unsuccesful dump to database");
     /// querier.remove_item("Test_Item");
     /// ```
-    pub fn remove_item(&self, item_name: &str) -> usize {
+    pub fn remove_item(&self, item_name: &str) -> Result<usize, QuerierError> {
         use crate::schema::items::dsl::*;
 
-        diesel::delete(items.find(item_name))
+        let rows = diesel::delete(items.find(item_name))
             .execute(&self.connection)
-            .expect("Failed to delete item.")
+            .map_err(|error| {
+                QuerierError::from_diesel(
+                    "items",
+                    Some(item_name.to_string()),
+                    error,
+                )
+            })?;
+        QuerierError::expect_rows("items", item_name.to_string(), rows)
     }
 
-    /// Given a querier instance, item name, and an Item struct to update the item with.
+    /// Given a querier instance, item name, and an Item struct to update the item with. Fails with NotFound if it does not exist.
     ///
     /// # Arguements
     ///
@@ -381,10 +391,16 @@ impl Querier {
     /// components: String::from("{'test': 'test'}"),
     /// });
     /// ```
-    pub fn update_item(&self, item_name: &str, updated_item: Item) -> usize {
+    pub fn update_item(
+        &self,
+        item_name: &str,
+        updated_item: Item,
+    ) -> Result<usize, QuerierError> {
         use crate::schema::items::dsl::*;
 
-        diesel::update(items.filter(name.eq(item_name)))
+        let new_name = updated_item.name.clone();
+
+        let rows = diesel::update(items.filter(name.eq(item_name)))
             .set((
                 name.eq(updated_item.name),
                 description.eq(updated_item.description),
@@ -392,7 +408,10 @@ impl Querier {
                 components.eq(updated_item.components),
             ))
             .execute(&self.connection)
-            .expect("Error updating item.")
+            .map_err(|error| {
+                QuerierError::from_diesel("items", Some(new_name), error)
+            })?;
+        QuerierError::expect_rows("items", item_name.to_string(), rows)
     }
 
     /// Given a querier instance query locations from the database instance. If all arguments are None it queries all locations.
@@ -417,7 +436,7 @@ impl Querier {
         name: Option<&str>,
         items: Option<Vec<&str>>,
         characters: Option<Vec<&str>>,
-    ) -> Vec<Location> {
+    ) -> Result<Vec<Location>, QuerierError> {
         use crate::schema::locations;
 
         let mut query = locations::table.into_boxed();
@@ -426,7 +445,7 @@ impl Querier {
             return query
                 .filter(locations::name.like(format!("%{}%", name)))
                 .load::<Location>(&self.connection)
-                .expect("Error loading items.");
+                .map_err(QuerierError::from);
         }
 
         if let Some(items) = items {
@@ -445,7 +464,7 @@ impl Querier {
 
         query
             .load::<Location>(&self.connection)
-            .expect("Error loading items.")
+            .map_err(QuerierError::from)
     }
 
     /// Given a querier instance and location name fetch it if it exists.
@@ -463,13 +482,22 @@ impl Querier {
     /// querier.dump_from_file("/path/to/data.json", FileType::JSON).expect("Unsuccesful dump to database");
     /// let location = querier.get_location("Test_Location");
     /// ```
-    pub fn get_location(&self, location_name: &str) -> Location {
+    pub fn get_location(
+        &self,
+        location_name: &str,
+    ) -> Result<Location, QuerierError> {
         use crate::schema::locations::dsl::*;
 
         locations
             .find(location_name)
             .get_result::<Location>(&self.connection)
-            .expect("Failed to get location.")
+            .map_err(|error| {
+                QuerierError::from_diesel(
+                    "locations",
+                    Some(location_name.to_string()),
+                    error,
+                )
+            })
     }
 
     /// Given a querier instance and location struct to insert into the database instance.
@@ -493,13 +521,22 @@ impl Querier {
     /// characters: String::from("umbra"),
     /// });
     /// ```
-    pub fn insert_location(&self, location: Location) -> usize {
+    pub fn insert_location(
+        &self,
+        location: Location,
+    ) -> Result<usize, QuerierError> {
         use crate::schema::locations::dsl::*;
 
         diesel::insert_into(locations)
             .values(&location)
             .execute(&self.connection)
-            .expect("Error inserting location.")
+            .map_err(|error| {
+                QuerierError::from_diesel(
+                    "locations",
+                    Some(location.name.clone()),
+                    error,
+                )
+            })
     }
 
     /// Given a querier instance and vector of location structs to be inserted into the database instance.
@@ -507,16 +544,21 @@ impl Querier {
     /// # Arguements
     ///
     /// * `locations` - The vector of location structs to be inserted.
-    pub fn insert_locations(&self, insert_locations: Vec<Location>) -> usize {
+    pub fn insert_locations(
+        &self,
+        insert_locations: Vec<Location>,
+    ) -> Result<usize, QuerierError> {
         use crate::schema::locations::dsl::*;
 
         diesel::insert_into(locations)
             .values(&insert_locations)
             .execute(&self.connection)
-            .expect("Error inserting locations.")
+            .map_err(|error| {
+                QuerierError::from_diesel("locations", None, error)
+            })
     }
 
-    /// Given a querier instance and location name remove it if it exists.
+    /// Given a querier instance and location name remove it. Fails with NotFound if it does not exist.
     ///
     /// # Arguements
     ///
@@ -531,15 +573,25 @@ impl Querier {
     /// querier.dump_from_file("/path/to/data.json", FileType::JSON).expect("Unsuccesful dump to database");
     /// querier.remove_location("Test_Location");
     /// ```
-    pub fn remove_location(&self, location_name: &str) -> usize {
+    pub fn remove_location(
+        &self,
+        location_name: &str,
+    ) -> Result<usize, QuerierError> {
         use crate::schema::locations::dsl::*;
 
-        diesel::delete(locations.find(location_name))
+        let rows = diesel::delete(locations.find(location_name))
             .execute(&self.connection)
-            .expect("Failed to delete location")
+            .map_err(|error| {
+                QuerierError::from_diesel(
+                    "locations",
+                    Some(location_name.to_string()),
+                    error,
+                )
+            })?;
+        QuerierError::expect_rows("locations", location_name.to_string(), rows)
     }
 
-    /// Given a querier instance, location name, and an Location struct to update the location with.
+    /// Given a querier instance, location name, and an Location struct to update the location with. Fails with NotFound if it does not exist.
     ///
     /// # Arguements
     ///
@@ -565,10 +617,12 @@ impl Querier {
         &self,
         location_name: &str,
         updated_location: Location,
-    ) -> usize {
+    ) -> Result<usize, QuerierError> {
         use crate::schema::locations::dsl::*;
 
-        diesel::update(locations.filter(name.eq(location_name)))
+        let new_name = updated_location.name.clone();
+
+        let rows = diesel::update(locations.filter(name.eq(location_name)))
             .set((
                 name.eq(updated_location.name),
                 description.eq(updated_location.description),
@@ -577,7 +631,10 @@ impl Querier {
                 characters.eq(updated_location.characters),
             ))
             .execute(&self.connection)
-            .expect("Error updating location.")
+            .map_err(|error| {
+                QuerierError::from_diesel("locations", Some(new_name), error)
+            })?;
+        QuerierError::expect_rows("locations", location_name.to_string(), rows)
     }
 
     /// Given a querier instance query characters from the database instance. If all arguments are None it queries all characters.
@@ -600,7 +657,7 @@ impl Querier {
         &self,
         name: Option<&str>,
         components: Option<Vec<&str>>,
-    ) -> Vec<Character> {
+    ) -> Result<Vec<Character>, QuerierError> {
         use crate::schema::characters;
 
         let mut query = characters::table.into_boxed();
@@ -609,7 +666,7 @@ impl Querier {
             return query
                 .filter(characters::name.like(format!("%{}%", name)))
                 .load::<Character>(&self.connection)
-                .expect("Error loading items.");
+                .map_err(QuerierError::from);
         }
 
         if let Some(comps) = components {
@@ -621,7 +678,7 @@ impl Querier {
 
         query
             .load::<Character>(&self.connection)
-            .expect("Error loading items.")
+            .map_err(QuerierError::from)
     }
 
     /// Given a querier instance and character name fetch it if it exists.
@@ -639,13 +696,22 @@ impl Querier {
     /// querier.dump_from_file("/path/to/data.json", FileType::JSON).expect("Unsuccesful dump to database");
     /// let character = querier.get_character("Test_Character");
     /// ```
-    pub fn get_character(&self, character_name: &str) -> Character {
+    pub fn get_character(
+        &self,
+        character_name: &str,
+    ) -> Result<Character, QuerierError> {
         use crate::schema::characters::dsl::*;
 
         characters
             .find(character_name)
             .get_result::<Character>(&self.connection)
-            .expect("Failed to get character.")
+            .map_err(|error| {
+                QuerierError::from_diesel(
+                    "characters",
+                    Some(character_name.to_string()),
+                    error,
+                )
+            })
     }
 
     /// Given a querier instance and character struct to insert into the database instance.
@@ -666,13 +732,22 @@ impl Querier {
     /// components: String::from("{ \"interactable\": true }"),
     /// });
     /// ```
-    pub fn insert_character(&self, character: Character) -> usize {
+    pub fn insert_character(
+        &self,
+        character: Character,
+    ) -> Result<usize, QuerierError> {
         use crate::schema::characters::dsl::*;
 
         diesel::insert_into(characters)
             .values(&character)
             .execute(&self.connection)
-            .expect("Error inserting character.")
+            .map_err(|error| {
+                QuerierError::from_diesel(
+                    "characters",
+                    Some(character.name.clone()),
+                    error,
+                )
+            })
     }
 
     /// Given a querier instance and vector of character structs to be inserted into the database instance.
@@ -683,16 +758,18 @@ impl Querier {
     pub fn insert_characters(
         &self,
         insert_characters: Vec<Character>,
-    ) -> usize {
+    ) -> Result<usize, QuerierError> {
         use crate::schema::characters::dsl::*;
 
         diesel::insert_into(characters)
             .values(&insert_characters)
             .execute(&self.connection)
-            .expect("Error inserting characters.")
+            .map_err(|error| {
+                QuerierError::from_diesel("characters", None, error)
+            })
     }
 
-    /// Given a querier instance and character name remove it if it exists.
+    /// Given a querier instance and character name remove it. Fails with NotFound if it does not exist.
     ///
     /// # Arguements
     ///
@@ -707,15 +784,29 @@ impl Querier {
     /// querier.dump_from_file("/path/to/data.json", FileType::JSON).expect("Unsuccesful dump to database");
     /// querier.remove_character("Test_Character");
     /// ```
-    pub fn remove_character(&self, character_name: &str) -> usize {
+    pub fn remove_character(
+        &self,
+        character_name: &str,
+    ) -> Result<usize, QuerierError> {
         use crate::schema::characters::dsl::*;
 
-        diesel::delete(characters.find(character_name))
+        let rows = diesel::delete(characters.find(character_name))
             .execute(&self.connection)
-            .expect("Failed to delete character")
+            .map_err(|error| {
+                QuerierError::from_diesel(
+                    "characters",
+                    Some(character_name.to_string()),
+                    error,
+                )
+            })?;
+        QuerierError::expect_rows(
+            "characters",
+            character_name.to_string(),
+            rows,
+        )
     }
 
-    /// Given a querier instance, character name, and an Character struct to update the character with.
+    /// Given a querier instance, character name, and an Character struct to update the character with. Fails with NotFound if it does not exist.
     ///
     /// # Arguements
     ///
@@ -738,16 +829,25 @@ impl Querier {
         &self,
         character_name: &str,
         updated_character: Character,
-    ) -> usize {
+    ) -> Result<usize, QuerierError> {
         use crate::schema::characters::dsl::*;
 
-        diesel::update(characters.filter(name.eq(character_name)))
+        let new_name = updated_character.name.clone();
+
+        let rows = diesel::update(characters.filter(name.eq(character_name)))
             .set((
                 name.eq(updated_character.name),
                 components.eq(updated_character.components),
             ))
             .execute(&self.connection)
-            .expect("Error updating character.")
+            .map_err(|error| {
+                QuerierError::from_diesel("characters", Some(new_name), error)
+            })?;
+        QuerierError::expect_rows(
+            "characters",
+            character_name.to_string(),
+            rows,
+        )
     }
 
     /// Given a querier instance query dialogues from the database instance. If all arguments are None it queries all dialogies.
@@ -774,7 +874,7 @@ impl Querier {
         flags: Option<Vec<&str>>,
         location: Option<&str>,
         dialogue_snippets: Option<Vec<&str>>,
-    ) -> Vec<Dialogue> {
+    ) -> Result<Vec<Dialogue>, QuerierError> {
         use crate::schema::dialogues;
 
         let mut query = dialogues::table.into_boxed();
@@ -809,7 +909,7 @@ impl Querier {
 
         query
             .load::<Dialogue>(&self.connection)
-            .expect("Error loading items.")
+            .map_err(QuerierError::from)
     }
 
     /// Given a querier instance and dialogue name fetch it if it exists.
@@ -827,13 +927,22 @@ impl Querier {
     /// querier.dump_from_file("/path/to/data.json", FileType::JSON).expect("Unsuccesful dump to database");
     /// let dialogue = querier.get_dialogue("Test_Dialogue");
     /// ```
-    pub fn get_dialogue(&self, dialogue_id: i32) -> Dialogue {
+    pub fn get_dialogue(
+        &self,
+        dialogue_id: i32,
+    ) -> Result<Dialogue, QuerierError> {
         use crate::schema::dialogues::dsl::*;
 
         dialogues
             .find(dialogue_id)
             .get_result::<Dialogue>(&self.connection)
-            .expect("Failed to get dialogue.")
+            .map_err(|error| {
+                QuerierError::from_diesel(
+                    "dialogues",
+                    Some(dialogue_id.to_string()),
+                    error,
+                )
+            })
     }
 
     /// Given a querier instance and dialogue struct to insert into the database instance.
@@ -854,13 +963,22 @@ impl Querier {
     /// components: String::from("{ \"interactable\": true }"),
     /// });
     /// ```
-    pub fn insert_dialogue(&self, dialogue_struct: Dialogue) -> usize {
+    pub fn insert_dialogue(
+        &self,
+        dialogue_struct: Dialogue,
+    ) -> Result<usize, QuerierError> {
         use crate::schema::dialogues::dsl::*;
 
         diesel::insert_into(dialogues)
             .values(&dialogue_struct)
             .execute(&self.connection)
-            .expect("Error inserting dialogue.")
+            .map_err(|error| {
+                QuerierError::from_diesel(
+                    "dialogues",
+                    Some(dialogue_struct.id.to_string()),
+                    error,
+                )
+            })
     }
 
     /// Given a querier instance and vector of dialogue structs to be inserted into the database instance.
@@ -868,16 +986,21 @@ impl Querier {
     /// # Arguements
     ///
     /// * `dialogues` - The vector of dialogue structs to be inserted.
-    pub fn insert_dialogues(&self, insert_dialogues: Vec<Dialogue>) -> usize {
+    pub fn insert_dialogues(
+        &self,
+        insert_dialogues: Vec<Dialogue>,
+    ) -> Result<usize, QuerierError> {
         use crate::schema::dialogues::dsl::*;
 
         diesel::insert_into(dialogues)
             .values(&insert_dialogues)
             .execute(&self.connection)
-            .expect("Error inserting dialogues.")
+            .map_err(|error| {
+                QuerierError::from_diesel("dialogues", None, error)
+            })
     }
 
-    /// Given a querier instance and dialogue name remove it if it exists.
+    /// Given a querier instance and dialogue name remove it. Fails with NotFound if it does not exist.
     ///
     /// # Arguements
     ///
@@ -892,15 +1015,25 @@ impl Querier {
     /// querier.dump_from_file("/path/to/data.json", FileType::JSON).expect("Unsuccesful dump to database");
     /// querier.remove_dialogue("Test_Dialogue");
     /// ```
-    pub fn remove_dialogue(&self, dialogue_id: i32) -> usize {
+    pub fn remove_dialogue(
+        &self,
+        dialogue_id: i32,
+    ) -> Result<usize, QuerierError> {
         use crate::schema::dialogues::dsl::*;
 
-        diesel::delete(dialogues.find(dialogue_id))
+        let rows = diesel::delete(dialogues.find(dialogue_id))
             .execute(&self.connection)
-            .expect("Failed to delete dialogue")
+            .map_err(|error| {
+                QuerierError::from_diesel(
+                    "dialogues",
+                    Some(dialogue_id.to_string()),
+                    error,
+                )
+            })?;
+        QuerierError::expect_rows("dialogues", dialogue_id.to_string(), rows)
     }
 
-    /// Given a querier instance, dialogue name, and an Dialogue struct to update the dialogue with.
+    /// Given a querier instance, dialogue name, and an Dialogue struct to update the dialogue with. Fails with NotFound if it does not exist.
     ///
     /// # Arguements
     ///
@@ -923,10 +1056,10 @@ impl Querier {
         &self,
         id_num: i32,
         updated_dialogue: Dialogue,
-    ) -> usize {
+    ) -> Result<usize, QuerierError> {
         use crate::schema::dialogues::dsl::*;
 
-        diesel::update(dialogues.filter(id.eq(id_num)))
+        let rows = diesel::update(dialogues.filter(id.eq(id_num)))
             .set((
                 characters.eq(&updated_dialogue.characters),
                 flags.eq(&updated_dialogue.flags),
@@ -934,7 +1067,14 @@ impl Querier {
                 dialogue.eq(updated_dialogue.dialogue_string()),
             ))
             .execute(&self.connection)
-            .expect("Error updating dialogue.")
+            .map_err(|error| {
+                QuerierError::from_diesel(
+                    "dialogues",
+                    Some(id_num.to_string()),
+                    error,
+                )
+            })?;
+        QuerierError::expect_rows("dialogues", id_num.to_string(), rows)
     }
 
     /// Given a querier instance and node name fetch it if it exists.
@@ -952,13 +1092,19 @@ impl Querier {
     /// querier.dump_from_file("/path/to/data.json", FileType::JSON).expect("Unsuccesful dump to database");
     /// let node = querier.get_node(0);
     /// ```
-    pub fn get_node(&self, node_id: i32) -> Node {
+    pub fn get_node(&self, node_id: i32) -> Result<Node, QuerierError> {
         use crate::schema::nodes::dsl::*;
 
         nodes
             .find(node_id)
             .get_result::<Node>(&self.connection)
-            .expect("Failed to get node.")
+            .map_err(|error| {
+                QuerierError::from_diesel(
+                    "nodes",
+                    Some(node_id.to_string()),
+                    error,
+                )
+            })
     }
 
     /// Given a querier instance and node struct to insert into the database instance.
@@ -979,13 +1125,19 @@ impl Querier {
     /// data: String::from("{\"story\":[{\"who\":\"dad\",\"what\":\"What the fuck do you think you were doing?\"},{\"who\":\"mom\",\"what\":\"You are are grounded!\"}],\"choices\":[{\"what\":\"Fuck you guys.\",\"next\":0},{\"what\":\"I was trying to help her.\",\"next\":1}],\"visited\":false}"),
     /// });
     /// ```
-    pub fn insert_node(&self, node: Node) -> usize {
+    pub fn insert_node(&self, node: Node) -> Result<usize, QuerierError> {
         use crate::schema::nodes::dsl::*;
 
         diesel::insert_into(nodes)
             .values(&node)
             .execute(&self.connection)
-            .expect("Error inserting node.")
+            .map_err(|error| {
+                QuerierError::from_diesel(
+                    "nodes",
+                    Some(node.id.to_string()),
+                    error,
+                )
+            })
     }
 
     /// Given a querier instance and vector of node structs to be inserted into the database instance.
@@ -993,16 +1145,19 @@ impl Querier {
     /// # Arguements
     ///
     /// * `nodes` - The vector of node structs to be inserted.
-    pub fn insert_nodes(&self, insert_nodes: Vec<Node>) -> usize {
+    pub fn insert_nodes(
+        &self,
+        insert_nodes: Vec<Node>,
+    ) -> Result<usize, QuerierError> {
         use crate::schema::nodes::dsl::*;
 
         diesel::insert_into(nodes)
             .values(&insert_nodes)
             .execute(&self.connection)
-            .expect("Error inserting nodes.")
+            .map_err(|error| QuerierError::from_diesel("nodes", None, error))
     }
 
-    /// Given a querier instance, node name, and an Node struct to update the node with.
+    /// Given a querier instance, node name, and an Node struct to update the node with. Fails with NotFound if it does not exist.
     ///
     /// # Arguements
     ///
@@ -1021,12 +1176,23 @@ impl Querier {
     /// data: String::from("{\"story\":[{\"who\":\"dad\",\"what\":\"What the fuck do you think you were doing?\"},{\"who\":\"mom\",\"what\":\"You are are grounded!\"}],\"choices\":[{\"what\":\"Fuck you guys.\",\"next\":0},{\"what\":\"I was trying to help her.\",\"next\":1}],\"visited\":false}"),
     /// });
     /// ```
-    pub fn update_node(&self, node_id: i32, updated_node: Node) -> usize {
+    pub fn update_node(
+        &self,
+        node_id: i32,
+        updated_node: Node,
+    ) -> Result<usize, QuerierError> {
         use crate::schema::nodes::dsl::*;
 
-        diesel::update(nodes.filter(id.eq(node_id)))
+        let rows = diesel::update(nodes.filter(id.eq(node_id)))
             .set((data.eq(updated_node.data),))
             .execute(&self.connection)
-            .expect("Error updating node.")
+            .map_err(|error| {
+                QuerierError::from_diesel(
+                    "nodes",
+                    Some(node_id.to_string()),
+                    error,
+                )
+            })?;
+        QuerierError::expect_rows("nodes", node_id.to_string(), rows)
     }
 }